@@ -0,0 +1,3 @@
+ALTER TABLE users DROP COLUMN decision_reason;
+ALTER TABLE users DROP COLUMN decision_date;
+ALTER TABLE users DROP COLUMN decided_by;
@@ -0,0 +1,3 @@
+ALTER TABLE users ADD COLUMN decided_by      INTEGER;
+ALTER TABLE users ADD COLUMN decision_date   DATETIME;
+ALTER TABLE users ADD COLUMN decision_reason TEXT;
@@ -0,0 +1 @@
+ALTER TABLE interviews ADD COLUMN channel_id INTEGER;
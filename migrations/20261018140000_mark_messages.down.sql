@@ -0,0 +1,3 @@
+ALTER TABLE users DROP COLUMN offending_message_id;
+ALTER TABLE users DROP COLUMN offending_channel_id;
+ALTER TABLE users DROP COLUMN mark_message_id;
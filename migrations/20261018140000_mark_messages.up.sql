@@ -0,0 +1,3 @@
+ALTER TABLE users ADD COLUMN mark_message_id      INTEGER;
+ALTER TABLE users ADD COLUMN offending_channel_id INTEGER;
+ALTER TABLE users ADD COLUMN offending_message_id INTEGER;
@@ -0,0 +1,2 @@
+ALTER TABLE users DROP COLUMN claim_date;
+ALTER TABLE users DROP COLUMN claimed_by;
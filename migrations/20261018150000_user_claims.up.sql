@@ -0,0 +1,2 @@
+ALTER TABLE users ADD COLUMN claimed_by INTEGER;
+ALTER TABLE users ADD COLUMN claim_date DATETIME;
@@ -0,0 +1 @@
+ALTER TABLE users ADD COLUMN last_offense_date DATETIME;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS channel_checkpoints (
+    channel_id  INTEGER PRIMARY KEY NOT NULL,
+
+    message_id  INTEGER NOT NULL,
+
+    update_date DATETIME NOT NULL DEFAULT (DATETIME('now'))
+);
@@ -0,0 +1,21 @@
+-- Interviews of tiers other than `text` and `id` can't be represented anymore.
+CREATE TABLE interviews_types (
+    user_id        INTEGER KEY NOT NULL,
+    interviewer_id INTEGER NOT NULL,
+
+    type           TEXT CHECK(type IN ('text', 'id')) NOT NULL,
+
+    interview_date DATETIME NOT NULL DEFAULT (DATETIME('now')),
+
+    channel_id     INTEGER,
+
+    PRIMARY KEY (user_id, interviewer_id),
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+);
+
+INSERT INTO interviews_types(user_id, interviewer_id, type, interview_date, channel_id)
+    SELECT user_id, interviewer_id, tier, interview_date, channel_id FROM interviews
+        WHERE tier IN ('text', 'id');
+
+DROP TABLE interviews;
+ALTER TABLE interviews_types RENAME TO interviews;
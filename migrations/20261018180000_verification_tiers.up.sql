@@ -0,0 +1,21 @@
+-- Interview types become configurable tiers, so the `CHECK` on them is dropped. Existing
+-- `text` and `id` interviews keep their names as tier names.
+CREATE TABLE interviews_tiers (
+    user_id        INTEGER NOT NULL,
+    interviewer_id INTEGER NOT NULL,
+
+    tier           TEXT NOT NULL,
+
+    interview_date DATETIME NOT NULL DEFAULT (DATETIME('now')),
+
+    channel_id     INTEGER,
+
+    PRIMARY KEY (user_id, interviewer_id),
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+);
+
+INSERT INTO interviews_tiers(user_id, interviewer_id, tier, interview_date, channel_id)
+    SELECT user_id, interviewer_id, type, interview_date, channel_id FROM interviews;
+
+DROP TABLE interviews;
+ALTER TABLE interviews_tiers RENAME TO interviews;
@@ -0,0 +1,22 @@
+-- Only the latest interview of a user by the same interviewer is kept.
+CREATE TABLE interviews_tiers (
+    user_id        INTEGER NOT NULL,
+    interviewer_id INTEGER NOT NULL,
+
+    tier           TEXT NOT NULL,
+
+    interview_date DATETIME NOT NULL DEFAULT (DATETIME('now')),
+
+    channel_id     INTEGER,
+
+    PRIMARY KEY (user_id, interviewer_id),
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+);
+
+INSERT OR REPLACE INTO interviews_tiers(user_id, interviewer_id, tier, interview_date, channel_id)
+    SELECT user_id, interviewer_id, tier, interview_date, channel_id FROM interviews
+        ORDER BY id ASC;
+
+DROP INDEX interviews_user_id;
+DROP TABLE interviews;
+ALTER TABLE interviews_tiers RENAME TO interviews;
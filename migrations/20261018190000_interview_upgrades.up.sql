@@ -0,0 +1,41 @@
+-- Interviews get their own IDs instead of one row per interviewer, so users can be
+-- interviewed again to upgrade their tier. Outcomes of existing interviews are derived from
+-- the status of their users, older interviews of the same user are considered reverted.
+CREATE TABLE interviews_upgrades (
+    id             INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+
+    user_id        INTEGER NOT NULL,
+    interviewer_id INTEGER NOT NULL,
+
+    tier           TEXT NOT NULL,
+    previous_tier  TEXT,
+
+    outcome        TEXT CHECK(outcome IN ('approved', 'rejected', 'reverted')),
+
+    interview_date DATETIME NOT NULL DEFAULT (DATETIME('now')),
+
+    channel_id     INTEGER,
+
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+);
+
+INSERT INTO interviews_upgrades(user_id, interviewer_id, tier, outcome, interview_date, channel_id)
+    SELECT interviews.user_id, interviewer_id, tier,
+            CASE
+                WHEN interviews.rowid != (
+                    SELECT latest.rowid FROM interviews AS latest
+                        WHERE latest.user_id = interviews.user_id
+                        ORDER BY latest.interview_date DESC, latest.rowid DESC LIMIT 1
+                ) THEN 'reverted'
+                WHEN users.status IN ('approved', 'rejected') THEN users.status
+                WHEN users.status = 'ongoing' THEN NULL
+                ELSE 'reverted'
+            END,
+            interview_date, channel_id
+        FROM interviews LEFT JOIN users ON users.user_id = interviews.user_id
+        ORDER BY interview_date ASC, interviews.rowid ASC;
+
+DROP TABLE interviews;
+ALTER TABLE interviews_upgrades RENAME TO interviews;
+
+CREATE INDEX interviews_user_id ON interviews(user_id);
@@ -0,0 +1,4 @@
+ALTER TABLE interviews DROP COLUMN notes;
+ALTER TABLE interviews DROP COLUMN reason;
+ALTER TABLE interviews DROP COLUMN end_date;
+ALTER TABLE interviews RENAME COLUMN start_date TO interview_date;
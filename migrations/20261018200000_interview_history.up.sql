@@ -0,0 +1,16 @@
+ALTER TABLE interviews RENAME COLUMN interview_date TO start_date;
+ALTER TABLE interviews ADD COLUMN end_date DATETIME;
+ALTER TABLE interviews ADD COLUMN reason   TEXT;
+ALTER TABLE interviews ADD COLUMN notes    TEXT;
+
+-- The decision about a user was made at the end of their latest decided interview.
+UPDATE interviews
+    SET end_date = (SELECT decision_date FROM users WHERE users.user_id = interviews.user_id),
+        reason = (SELECT decision_reason FROM users WHERE users.user_id = interviews.user_id)
+    WHERE outcome IN ('approved', 'rejected')
+        AND id = (
+            SELECT latest.id FROM interviews AS latest
+                WHERE latest.user_id = interviews.user_id
+                    AND latest.outcome IN ('approved', 'rejected')
+                ORDER BY latest.start_date DESC, latest.id DESC LIMIT 1
+        );
@@ -0,0 +1,19 @@
+-- Every verification action, so what happened to a user can be traced without the logs.
+CREATE TABLE audit_log (
+    id            INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+
+    action        TEXT NOT NULL,
+
+    -- Actions taken by the bot itself have no actor.
+    actor_id      INTEGER,
+    target_id     INTEGER NOT NULL,
+
+    status_before TEXT CHECK(status_before IN ('pending', 'ongoing', 'approved', 'rejected')),
+    status_after  TEXT CHECK(status_after IN ('pending', 'ongoing', 'approved', 'rejected')),
+
+    reason        TEXT,
+
+    date          DATETIME NOT NULL DEFAULT (DATETIME('now'))
+);
+
+CREATE INDEX audit_log_target_id ON audit_log(target_id);
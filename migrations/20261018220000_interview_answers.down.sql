@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS interviews_channel_id;
+DROP TABLE IF EXISTS interview_answers;
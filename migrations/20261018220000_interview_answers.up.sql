@@ -0,0 +1,17 @@
+-- Answers of interviewees to the questions of their tier, one row per question. The
+-- question is stored along with the answer, since the questions can change in the config.
+CREATE TABLE interview_answers (
+    interview_id INTEGER NOT NULL,
+    position     INTEGER NOT NULL,
+
+    question     TEXT NOT NULL,
+    answer       TEXT NOT NULL,
+
+    answer_date  DATETIME NOT NULL DEFAULT (DATETIME('now')),
+
+    PRIMARY KEY (interview_id, position),
+    FOREIGN KEY (interview_id) REFERENCES interviews(id)
+);
+
+-- Messages in interview channels are looked up by their channel.
+CREATE INDEX interviews_channel_id ON interviews(channel_id);
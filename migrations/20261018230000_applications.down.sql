@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS applications_user_id;
+DROP TABLE IF EXISTS applications;
@@ -0,0 +1,16 @@
+-- Application forms submitted by users through `/apply` before their interview.
+CREATE TABLE applications (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+
+    user_id         INTEGER NOT NULL,
+
+    age             INTEGER NOT NULL,
+    source          TEXT NOT NULL,
+    reason          TEXT NOT NULL,
+
+    submission_date DATETIME NOT NULL DEFAULT (DATETIME('now')),
+
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+);
+
+CREATE INDEX applications_user_id ON applications(user_id);
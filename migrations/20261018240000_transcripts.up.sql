@@ -0,0 +1,17 @@
+-- Messages of interview channels, captured when the interviews end.
+CREATE TABLE transcript_messages (
+    interview_id INTEGER NOT NULL,
+    message_id   INTEGER NOT NULL,
+
+    author_id    INTEGER NOT NULL,
+    author_name  TEXT NOT NULL,
+
+    content      TEXT NOT NULL,
+    -- File names of the attachments, one per line.
+    attachments  TEXT,
+
+    message_date DATETIME NOT NULL,
+
+    PRIMARY KEY (interview_id, message_id),
+    FOREIGN KEY (interview_id) REFERENCES interviews(id)
+);
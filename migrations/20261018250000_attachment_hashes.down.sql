@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS attachment_hashes_sha256;
+DROP TABLE IF EXISTS attachment_hashes;
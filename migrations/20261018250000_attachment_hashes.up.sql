@@ -0,0 +1,19 @@
+-- Hashes of attachments posted during ID verification interviews, kept in place of the
+-- attachments themselves, which are deleted when the interviews end.
+CREATE TABLE attachment_hashes (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+
+    interview_id INTEGER NOT NULL,
+    user_id      INTEGER NOT NULL,
+
+    sha256       TEXT NOT NULL,
+    size         INTEGER NOT NULL,
+    content_type TEXT,
+
+    posted_date  DATETIME NOT NULL,
+    purge_date   DATETIME NOT NULL DEFAULT (DATETIME('now')),
+
+    FOREIGN KEY (interview_id) REFERENCES interviews(id)
+);
+
+CREATE INDEX attachment_hashes_sha256 ON attachment_hashes(sha256);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS image_hashes_user_id;
+DROP TABLE IF EXISTS image_hashes;
@@ -0,0 +1,16 @@
+-- Perceptual hashes of images posted during ID verification interviews, compared against
+-- the images of other users to catch reused ID photos.
+CREATE TABLE image_hashes (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+
+    interview_id    INTEGER NOT NULL,
+    user_id         INTEGER NOT NULL,
+
+    perceptual_hash INTEGER NOT NULL,
+
+    posted_date     DATETIME NOT NULL,
+
+    FOREIGN KEY (interview_id) REFERENCES interviews(id)
+);
+
+CREATE INDEX image_hashes_user_id ON image_hashes(user_id);
@@ -0,0 +1,9 @@
+-- ID verification interviews whose attachments still have to be purged. Rows are removed
+-- once a purge succeeds, so failed ones are retried until then.
+CREATE TABLE attachment_purges (
+    interview_id INTEGER PRIMARY KEY NOT NULL,
+
+    request_date DATETIME NOT NULL DEFAULT (DATETIME('now')),
+
+    FOREIGN KEY (interview_id) REFERENCES interviews(id)
+);
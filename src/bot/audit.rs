@@ -0,0 +1,110 @@
+//! Rendering of the audit log, for the history command and the CLI export.
+
+use std::io::Write;
+
+use sqlx::SqlitePool;
+
+use super::database::{AuditAction, AuditEntry, UserStatus};
+
+const fn action_name(action: AuditAction) -> &'static str {
+    match action {
+        AuditAction::Marked => "marked",
+        AuditAction::Applied => "applied",
+        AuditAction::Claimed => "claimed",
+        AuditAction::InterviewStarted => "interview started",
+        AuditAction::Approved => "approved",
+        AuditAction::Rejected => "rejected",
+        AuditAction::Reverted => "reverted",
+        AuditAction::Reopened => "reopened",
+        AuditAction::RoleChanged => "role changed",
+        AuditAction::Restored => "restored",
+        AuditAction::Override => "override",
+    }
+}
+
+fn status_name(status: Option<UserStatus>) -> String {
+    status.map_or_else(String::new, |status| status.to_string())
+}
+
+/// Describes an entry in a line of a Discord message.
+pub fn describe(entry: &AuditEntry) -> String {
+    let mut line = format!(
+        "<t:{}:f> **{}**",
+        entry.date.and_utc().timestamp(),
+        action_name(entry.action)
+    );
+    match entry.actor_id {
+        Some(actor_id) => line.push_str(&format!(" by <@{actor_id}>")),
+        None => line.push_str(" by the bot"),
+    }
+    if let Some(status_after) = entry.status_after {
+        line.push_str(&format!(
+            ", `{}` → `{status_after}`",
+            entry
+                .status_before
+                .map_or_else(|| "none".to_string(), |status| status.to_string())
+        ));
+    }
+    if let Some(reason) = &entry.reason {
+        line.push_str(&format!(": {reason}"));
+    }
+
+    line
+}
+
+/// Quotes a CSV field if it contains anything that would break the row.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Writes the whole audit log as CSV, oldest entries first.
+pub async fn export(database: &SqlitePool, writer: &mut impl Write) -> anyhow::Result<()> {
+    writeln!(
+        writer,
+        "id,date,action,actor_id,target_id,status_before,status_after,reason"
+    )?;
+
+    for entry in AuditEntry::fetch_all(database).await? {
+        let fields = [
+            entry.id.to_string(),
+            entry.date.format("%Y-%m-%d %H:%M:%S").to_string(),
+            action_name(entry.action).to_string(),
+            entry
+                .actor_id
+                .map_or_else(String::new, |actor_id| actor_id.to_string()),
+            entry.target_id.to_string(),
+            status_name(entry.status_before),
+            status_name(entry.status_after),
+            entry.reason.unwrap_or_default(),
+        ];
+        let row = fields
+            .iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<_>>()
+            .join(",");
+        writeln!(writer, "{row}")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::csv_field;
+
+    #[test]
+    fn leaves_plain_fields_as_is() {
+        assert_eq!(csv_field("Interview timed out."), "Interview timed out.");
+    }
+
+    #[test]
+    fn quotes_fields_with_separators() {
+        assert_eq!(csv_field("too young, lied"), "\"too young, lied\"");
+        assert_eq!(csv_field("said \"hi\""), "\"said \"\"hi\"\"\"");
+        assert_eq!(csv_field("one\ntwo"), "\"one\ntwo\"");
+    }
+}
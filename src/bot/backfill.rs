@@ -0,0 +1,143 @@
+//! Catching up on messages posted in watched channels while the bot was offline.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serenity::all::{ChannelType, Context, GetMessages, GuildChannel, MessageId, MessageType};
+use tracing::{debug, info, trace, warn};
+
+use super::{
+    database::{self, ChannelCheckpoint},
+    event_handler::BouncerEventHandler,
+};
+
+/// Milliseconds between the Unix epoch and the Discord epoch, which snowflakes count from.
+const DISCORD_EPOCH_MILLISECONDS: i64 = 1_420_070_400_000;
+
+/// The newest message ID Discord could have given out by the given date.
+pub fn message_id_at(date: DateTime<Utc>) -> MessageId {
+    let milliseconds =
+        u64::try_from(date.timestamp_millis() - DISCORD_EPOCH_MILLISECONDS).unwrap_or_default();
+    MessageId::new(((milliseconds + 1) << 22) - 1)
+}
+
+/// Runs the marking logic over every message posted in watched channels between their
+/// checkpoints and `until`, newer messages are handled live.
+pub async fn run(
+    handler: BouncerEventHandler,
+    context: Context,
+    checkpoints: Vec<ChannelCheckpoint>,
+    until: MessageId,
+) {
+    let checkpoints: HashMap<i64, i64> = checkpoints
+        .into_iter()
+        .map(|checkpoint| (checkpoint.channel_id, checkpoint.message_id))
+        .collect();
+
+    //* Threads, forum posts included, aren't listed with the other channels.
+    let channels: Vec<GuildChannel> = {
+        let state = handler.state.read().await;
+        let guild = &state.context.guild;
+        guild
+            .channels
+            .iter()
+            .chain(guild.threads.iter())
+            .filter(|channel| is_backfilled(channel.kind) && handler.is_watched(channel))
+            .cloned()
+            .collect()
+    };
+
+    trace!("backfilling {} watched channels...", channels.len());
+    for channel in &channels {
+        let checkpoint = checkpoints.get(&database::to_id(channel.id.get())).copied();
+        if let Err(error) = backfill_channel(&handler, &context, channel, checkpoint, until).await {
+            warn!("could not backfill the channel `{}`: {error}", channel.id);
+        }
+    }
+    trace!("backfilled the watched channels");
+}
+
+/// Whether messages of the channel type can be caught up on.
+const fn is_backfilled(kind: ChannelType) -> bool {
+    matches!(
+        kind,
+        ChannelType::Text
+            | ChannelType::News
+            | ChannelType::PublicThread
+            | ChannelType::PrivateThread
+            | ChannelType::NewsThread
+    )
+}
+
+async fn backfill_channel(
+    handler: &BouncerEventHandler,
+    context: &Context,
+    channel: &GuildChannel,
+    checkpoint: Option<i64>,
+    until: MessageId,
+) -> anyhow::Result<()> {
+    let channel_id = database::to_id(channel.id.get());
+
+    let Some(checkpoint) = checkpoint else {
+        //* There is nothing to bound the scan with, so only start keeping track from now on.
+        if let Some(last_message_id) = channel.last_message_id {
+            ChannelCheckpoint::advance(
+                &handler.state.read().await.database,
+                channel_id,
+                database::to_id(last_message_id.get().min(until.get())),
+            )
+            .await?;
+        }
+        debug!(
+            "channel `{}` has no checkpoint yet, skipping...",
+            channel.id
+        );
+        return Ok(());
+    };
+
+    let mut after = MessageId::new(database::from_id(checkpoint));
+    let mut processed = 0;
+    let mut caught_up = false;
+    while !caught_up {
+        let mut messages = channel
+            .id
+            .messages(&context.http, GetMessages::new().after(after).limit(100))
+            .await?;
+        if messages.is_empty() {
+            break;
+        }
+        messages.sort_by_key(|message| message.id);
+        caught_up = messages.len() < 100;
+
+        let state = handler.state.read().await;
+        for mut message in messages {
+            if message.id > until {
+                caught_up = true;
+                break;
+            }
+            after = message.id;
+            if message.kind != MessageType::Regular || message.author.bot() {
+                continue;
+            }
+
+            //* Messages fetched over HTTP don't carry their guild.
+            message.guild_id = Some(state.context.guild.id);
+            handler
+                .process_message(context, &state, &message, channel)
+                .await;
+            processed += 1;
+        }
+
+        ChannelCheckpoint::advance(&state.database, channel_id, database::to_id(after.get()))
+            .await?;
+    }
+
+    if processed > 0 {
+        info!(
+            "caught up on {processed} messages posted in `{}` while offline",
+            channel.id
+        );
+    }
+
+    Ok(())
+}
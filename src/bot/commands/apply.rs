@@ -0,0 +1,37 @@
+use serenity::builder::CreateCommand;
+
+use crate::bot::{
+    components, helpers::interaction_context::CommandInteractionContext, lifecycle, BouncerState,
+};
+
+use super::BouncerCommand;
+
+pub struct Command;
+impl<'a> BouncerCommand<'a> for Command {
+    const COMMAND_NAME: &'a str = "apply";
+    const COMMAND_DESCRIPTION: &'a str = "Apply for an interview.";
+
+    fn command() -> CreateCommand<'a> {
+        CreateCommand::new(Self::COMMAND_NAME).description(Self::COMMAND_DESCRIPTION)
+    }
+
+    async fn execute(
+        interaction_context: CommandInteractionContext<'_>,
+        state: &BouncerState,
+    ) -> anyhow::Result<()> {
+        //* Checked before showing the form too, so users don't fill it in for nothing.
+        match lifecycle::can_apply(state, interaction_context.interaction.user.id).await {
+            Ok(()) => {
+                interaction_context
+                    .reply_modal(components::apply::modal())
+                    .await?;
+            }
+            Err(error) if error.is_user_facing() => {
+                interaction_context.reply_string(error, Some(true)).await?;
+            }
+            Err(error) => return Err(error.into()),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use serenity::{
+    all::{CommandOptionType, CreateCommandOption, Mentionable, Permissions, RoleId},
+    builder::CreateCommand,
+};
+
+use crate::bot::{
+    extensions::resolved_options::ResolvedOptionExt,
+    helpers::interaction_context::CommandInteractionContext, lifecycle, BouncerState,
+};
+
+use super::BouncerCommand;
+
+pub struct Command;
+impl<'a> BouncerCommand<'a> for Command {
+    const COMMAND_NAME: &'a str = "approve";
+    const COMMAND_DESCRIPTION: &'a str = "Approve the ongoing interview of a user.";
+
+    fn command() -> CreateCommand<'a> {
+        CreateCommand::new(Self::COMMAND_NAME)
+            .description(Self::COMMAND_DESCRIPTION)
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::User, "user", "The user to approve.")
+                    .required(true),
+            )
+            .default_member_permissions(Permissions::MANAGE_ROLES)
+    }
+
+    async fn execute(
+        interaction_context: CommandInteractionContext<'_>,
+        state: &BouncerState,
+    ) -> anyhow::Result<()> {
+        let user = match interaction_context.options.get_user_and_member(0) {
+            Some((user, _)) => user,
+            None => unreachable!("The user option is required."),
+        };
+
+        let decider = &interaction_context.interaction.user;
+        let decider_role_ids: &[RoleId] = match &interaction_context.interaction.member {
+            Some(member) => &member.roles,
+            None => &[],
+        };
+        //* Swapping roles, capturing the transcript and closing the channel can take longer than
+        //* Discord waits for a response.
+        interaction_context.defer(None).await?;
+
+        match lifecycle::approve_interview(
+            &interaction_context.context.http,
+            state,
+            user.id,
+            decider.id,
+            decider_role_ids,
+        )
+        .await
+        {
+            Ok(tier_label) => {
+                interaction_context
+                    .edit_reply_string(format!(
+                        "{} has been approved by {} (`{tier_label}`).",
+                        user.id.mention(),
+                        decider.id.mention()
+                    ))
+                    .await?;
+            }
+            Err(error) if error.is_user_facing() => {
+                interaction_context.edit_reply_string(error).await?;
+            }
+            Err(error) => {
+                interaction_context
+                    .edit_reply_string("An unexpected error occurred, please try again.")
+                    .await?;
+                return Err(error.into());
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use serenity::{
+    all::{CommandOptionType, CreateCommandOption, Mentionable, Permissions},
+    builder::CreateCommand,
+};
+
+use crate::bot::{
+    extensions::resolved_options::ResolvedOptionExt,
+    helpers::interaction_context::CommandInteractionContext, lifecycle, BouncerState,
+};
+
+use super::BouncerCommand;
+
+pub struct Command;
+impl<'a> BouncerCommand<'a> for Command {
+    const COMMAND_NAME: &'a str = "claim";
+    const COMMAND_DESCRIPTION: &'a str = "Claim a user waiting for an interview.";
+
+    fn command() -> CreateCommand<'a> {
+        CreateCommand::new(Self::COMMAND_NAME)
+            .description(Self::COMMAND_DESCRIPTION)
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::User, "user", "The user to claim.")
+                    .required(true),
+            )
+            .default_member_permissions(Permissions::MANAGE_ROLES)
+    }
+
+    async fn execute(
+        interaction_context: CommandInteractionContext<'_>,
+        state: &BouncerState,
+    ) -> anyhow::Result<()> {
+        let user = match interaction_context.options.get_user_and_member(0) {
+            Some((user, _)) => user,
+            None => unreachable!("The user option is required."),
+        };
+
+        let interviewer = &interaction_context.interaction.user;
+        if user.id == interviewer.id {
+            interaction_context
+                .reply_string("You cannot claim yourself.", Some(true))
+                .await?;
+            return Ok(());
+        }
+
+        match lifecycle::claim_user(
+            &interaction_context.context.http,
+            state,
+            user.id,
+            interviewer.id,
+        )
+        .await
+        {
+            Ok(()) => {
+                interaction_context
+                    .reply_string(
+                        format!(
+                            "{} has been claimed by {}.",
+                            user.id.mention(),
+                            interviewer.id.mention()
+                        ),
+                        None,
+                    )
+                    .await?;
+            }
+            Err(error) if error.is_user_facing() => {
+                interaction_context.reply_string(error, Some(true)).await?;
+            }
+            Err(error) => return Err(error.into()),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use serenity::{
+    all::{CommandOptionType, CreateCommandOption, Mentionable, Permissions},
+    builder::CreateCommand,
+};
+
+use crate::bot::{
+    audit,
+    database::{self, AuditEntry},
+    extensions::resolved_options::ResolvedOptionExt,
+    helpers::interaction_context::CommandInteractionContext,
+    BouncerState,
+};
+
+use super::BouncerCommand;
+
+pub struct Command;
+impl<'a> BouncerCommand<'a> for Command {
+    const COMMAND_NAME: &'a str = "history";
+    const COMMAND_DESCRIPTION: &'a str = "Show the verification actions taken on a user.";
+
+    fn command() -> CreateCommand<'a> {
+        CreateCommand::new(Self::COMMAND_NAME)
+            .description(Self::COMMAND_DESCRIPTION)
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "user",
+                    "The user to show the history of.",
+                )
+                .required(true),
+            )
+            .default_member_permissions(Permissions::MANAGE_ROLES)
+    }
+
+    async fn execute(
+        interaction_context: CommandInteractionContext<'_>,
+        state: &BouncerState,
+    ) -> anyhow::Result<()> {
+        let user = match interaction_context.options.get_user_and_member(0) {
+            Some((user, _)) => user,
+            None => unreachable!("The user option is required."),
+        };
+
+        let entries =
+            AuditEntry::fetch_latest(&state.database, database::to_id(user.id.get()), 25).await?;
+        if entries.is_empty() {
+            interaction_context
+                .reply_string(
+                    format!("No actions were taken on {} yet.", user.id.mention()),
+                    Some(true),
+                )
+                .await?;
+            return Ok(());
+        }
+
+        //* The latest entries that fit into a Discord message, listed oldest first.
+        let mut history = format!("History of {}:", user.id.mention());
+        let mut length = history.len();
+        let mut lines = Vec::new();
+        for entry in &entries {
+            let line = format!("\n- {}", audit::describe(entry));
+            length += line.len();
+            if length > 1900 {
+                break;
+            }
+            lines.push(line);
+        }
+        lines.reverse();
+        history.extend(lines);
+
+        interaction_context
+            .reply_string(history, Some(true))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,16 +1,34 @@
 use serenity::{
-    all::{CommandOptionType, CreateCommandOption, Mentionable, Permissions},
+    all::{CommandOptionType, CreateCommandOption, Mentionable, Permissions, RoleId},
     builder::CreateCommand,
 };
 
 use crate::bot::{
-    extensions::resolved_options::ResolvedOptionExt,
-    helpers::interaction_context::CommandInteractionContext, BouncerState,
+    context::Tier, extensions::resolved_options::ResolvedOptionExt,
+    helpers::interaction_context::CommandInteractionContext, lifecycle, BouncerState,
 };
 
 use super::BouncerCommand;
 
 pub struct Command;
+
+impl Command {
+    /// The command with the configured tiers as the choices of its type option.
+    pub fn command_with_tiers<'a>(tiers: &[Tier]) -> CreateCommand<'a> {
+        let tier_option = tiers.iter().fold(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "type",
+                "The tier of the interview.",
+            )
+            .required(true),
+            |option, tier| option.add_string_choice(tier.label.clone(), tier.name.clone()),
+        );
+
+        Self::command().add_option(tier_option)
+    }
+}
+
 impl<'a> BouncerCommand<'a> for Command {
     const COMMAND_NAME: &'a str = "interview";
     const COMMAND_DESCRIPTION: &'a str = "Interview a user.";
@@ -22,16 +40,6 @@ impl<'a> BouncerCommand<'a> for Command {
                 CreateCommandOption::new(CommandOptionType::User, "user", "The user to interview.")
                     .required(true),
             )
-            .add_option(
-                CreateCommandOption::new(
-                    CommandOptionType::String,
-                    "type",
-                    "The type of the interview.",
-                )
-                .add_string_choice("Text", "text")
-                .add_string_choice("ID", "id")
-                .required(true),
-            )
             .default_member_permissions(Permissions::MANAGE_ROLES)
     }
 
@@ -43,15 +51,28 @@ impl<'a> BouncerCommand<'a> for Command {
             Some((user, Some(member))) => (user, member),
             Some((_, None)) => {
                 interaction_context
-                    .reply_string("This user does not seem to be a member of the server.", Some(true))
+                    .reply_string(
+                        "This user does not seem to be a member of the server.",
+                        Some(true),
+                    )
                     .await?;
                 return Ok(());
             }
             None => unreachable!("The user option is required."),
         };
-        let interview_type = match interaction_context.options.get_string_option("type") {
-            Some(interview_type) => interview_type,
-            None => unreachable!("The type option is required."),
+        let Some(tier_name) = interaction_context.options.get_string_option("type") else {
+            unreachable!("The type option is required.");
+        };
+        let interviewer_role_ids: &[RoleId] = match &interaction_context.interaction.member {
+            Some(member) => &member.roles,
+            None => &[],
+        };
+        let tier = match lifecycle::resolve_tier(state, tier_name, interviewer_role_ids) {
+            Ok(tier) => tier,
+            Err(error) => {
+                interaction_context.reply_string(error, Some(true)).await?;
+                return Ok(());
+            }
         };
 
         // TODO: Create a helper function for those.
@@ -78,15 +99,40 @@ impl<'a> BouncerCommand<'a> for Command {
             return Ok(());
         }
 
-        interaction_context
-            .reply_string(
-                format!(
-                    "{} will be interviewed by `{interview_type}`",
-                    user.id.mention()
-                ),
-                None,
-            )
-            .await?;
+        let interviewer = &interaction_context.interaction.user;
+        //* Swapping roles and opening a channel can take longer than Discord waits for a response.
+        interaction_context.defer(None).await?;
+
+        match lifecycle::start_interview(
+            &interaction_context.context.http,
+            state,
+            user.id,
+            interviewer.id,
+            tier,
+        )
+        .await
+        {
+            Ok(channel_id) => {
+                interaction_context
+                    .edit_reply_string(format!(
+                        "{} is now being interviewed by {} (`{}`) in {}.",
+                        user.id.mention(),
+                        interviewer.id.mention(),
+                        tier.label,
+                        channel_id.mention()
+                    ))
+                    .await?;
+            }
+            Err(error) if error.is_user_facing() => {
+                interaction_context.edit_reply_string(error).await?;
+            }
+            Err(error) => {
+                interaction_context
+                    .edit_reply_string("An unexpected error occurred, please try again.")
+                    .await?;
+                return Err(error.into());
+            }
+        }
 
         Ok(())
     }
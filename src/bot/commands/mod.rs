@@ -1,14 +1,24 @@
 use std::sync::Arc;
 
-use serenity::all::{Context, CreateCommand, Guild};
+use serenity::all::{Context, CreateCommand};
 use tokio::sync::RwLock;
 use tracing::{error, info, trace};
 
-use super::{helpers::interaction_context::CommandInteractionContext, BouncerState};
+use super::{
+    context::BouncerContext, helpers::interaction_context::CommandInteractionContext, BouncerState,
+};
 
+mod apply;
+mod approve;
+mod claim;
 mod dob;
+mod history;
 mod interview;
 mod meow;
+mod queue;
+mod reconcile;
+mod reject;
+mod transcript;
 
 pub trait BouncerCommand<'a> {
     const COMMAND_NAME: &'a str;
@@ -24,16 +34,28 @@ pub trait BouncerCommand<'a> {
     ) -> anyhow::Result<()>;
 }
 
-pub async fn register_commands(guild: &Guild, context: &Context) {
+pub async fn register_commands(bouncer_context: &BouncerContext, context: &Context) {
     trace!("registering guild commands...");
 
     let commands = &[
         meow::Command::command(),
         dob::Command::command(),
-        interview::Command::command(),
+        interview::Command::command_with_tiers(&bouncer_context.tiers),
+        approve::Command::command(),
+        reject::Command::command(),
+        queue::Command::command(),
+        claim::Command::command(),
+        reconcile::Command::command(),
+        history::Command::command(),
+        apply::Command::command(),
+        transcript::Command::command(),
     ];
 
-    match guild.set_commands(&context.http, commands).await {
+    match bouncer_context
+        .guild
+        .set_commands(&context.http, commands)
+        .await
+    {
         Ok(commands) => info!(
             "registered guild commands: `{}`",
             commands
@@ -63,6 +85,30 @@ pub async fn run_command(
         interview::Command::COMMAND_NAME => {
             interview::Command::execute(interaction_context, &*state.read().await).await
         }
+        approve::Command::COMMAND_NAME => {
+            approve::Command::execute(interaction_context, &*state.read().await).await
+        }
+        reject::Command::COMMAND_NAME => {
+            reject::Command::execute(interaction_context, &*state.read().await).await
+        }
+        queue::Command::COMMAND_NAME => {
+            queue::Command::execute(interaction_context, &*state.read().await).await
+        }
+        claim::Command::COMMAND_NAME => {
+            claim::Command::execute(interaction_context, &*state.read().await).await
+        }
+        reconcile::Command::COMMAND_NAME => {
+            reconcile::Command::execute(interaction_context, &*state.read().await).await
+        }
+        history::Command::COMMAND_NAME => {
+            history::Command::execute(interaction_context, &*state.read().await).await
+        }
+        apply::Command::COMMAND_NAME => {
+            apply::Command::execute(interaction_context, &*state.read().await).await
+        }
+        transcript::Command::COMMAND_NAME => {
+            transcript::Command::execute(interaction_context, &*state.read().await).await
+        }
         _ => Ok(()),
     };
 
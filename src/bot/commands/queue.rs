@@ -0,0 +1,30 @@
+use serenity::{all::Permissions, builder::CreateCommand};
+
+use crate::bot::{
+    components, helpers::interaction_context::CommandInteractionContext, BouncerState,
+};
+
+use super::BouncerCommand;
+
+pub struct Command;
+impl<'a> BouncerCommand<'a> for Command {
+    const COMMAND_NAME: &'a str = "queue";
+    const COMMAND_DESCRIPTION: &'a str = "List the users waiting for an interview.";
+
+    fn command() -> CreateCommand<'a> {
+        CreateCommand::new(Self::COMMAND_NAME)
+            .description(Self::COMMAND_DESCRIPTION)
+            .default_member_permissions(Permissions::MANAGE_ROLES)
+    }
+
+    async fn execute(
+        interaction_context: CommandInteractionContext<'_>,
+        state: &BouncerState,
+    ) -> anyhow::Result<()> {
+        interaction_context
+            .reply(components::queue::page(state, 0).await?.ephemeral(true))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use serenity::{
+    all::{CommandOptionType, CreateCommandOption, Permissions},
+    builder::CreateCommand,
+};
+
+use crate::bot::{
+    extensions::resolved_options::ResolvedOptionExt,
+    helpers::interaction_context::CommandInteractionContext, reconcile, BouncerState,
+};
+
+use super::BouncerCommand;
+
+/// How many roles `/reconcile fix:true` removes at most, unless given a limit.
+const DEFAULT_REMOVAL_LIMIT: usize = 10;
+
+pub struct Command;
+impl<'a> BouncerCommand<'a> for Command {
+    const COMMAND_NAME: &'a str = "reconcile";
+    const COMMAND_DESCRIPTION: &'a str =
+        "Compare the roles of every member with their interview records.";
+
+    fn command() -> CreateCommand<'a> {
+        CreateCommand::new(Self::COMMAND_NAME)
+            .description(Self::COMMAND_DESCRIPTION)
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "fix",
+                "Whether to fix the inconsistencies instead of only reporting them.",
+            ))
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "limit",
+                    "How many roles fixing can remove at most, 10 by default.",
+                )
+                .min_int_value(1),
+            )
+            .default_member_permissions(Permissions::MANAGE_ROLES)
+    }
+
+    async fn execute(
+        interaction_context: CommandInteractionContext<'_>,
+        state: &BouncerState,
+    ) -> anyhow::Result<()> {
+        let fix = interaction_context
+            .options
+            .get_bool_option("fix")
+            .unwrap_or(false);
+        let removal_limit = interaction_context
+            .options
+            .get_integer_option("limit")
+            .map_or(DEFAULT_REMOVAL_LIMIT, |limit| {
+                usize::try_from(limit).unwrap_or_default()
+            });
+
+        //* Walking every member can take longer than Discord waits for a response.
+        interaction_context.defer(Some(true)).await?;
+
+        let report = reconcile::run(
+            &interaction_context.context.http,
+            state,
+            fix.then_some(removal_limit),
+        )
+        .await?;
+        interaction_context
+            .edit_reply_string(report.summary())
+            .await?;
+
+        Ok(())
+    }
+}
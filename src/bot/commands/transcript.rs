@@ -0,0 +1,104 @@
+use serenity::{
+    all::{
+        CommandOptionType, CreateAttachment, CreateCommandOption, CreateInteractionResponseMessage,
+        Mentionable, Permissions,
+    },
+    builder::CreateCommand,
+};
+
+use crate::bot::{
+    database::{self, Interview},
+    extensions::resolved_options::ResolvedOptionExt,
+    helpers::interaction_context::CommandInteractionContext,
+    transcript::{self, Format},
+    BouncerState,
+};
+
+use super::BouncerCommand;
+
+pub struct Command;
+impl<'a> BouncerCommand<'a> for Command {
+    const COMMAND_NAME: &'a str = "transcript";
+    const COMMAND_DESCRIPTION: &'a str = "Export the transcript of an interview of a user.";
+
+    fn command() -> CreateCommand<'a> {
+        CreateCommand::new(Self::COMMAND_NAME)
+            .description(Self::COMMAND_DESCRIPTION)
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "user",
+                    "The user to export the transcript of.",
+                )
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "interview",
+                    "The ID of the interview, the latest one if not specified.",
+                )
+                .min_int_value(1),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "format",
+                    "The format of the transcript, Markdown if not specified.",
+                )
+                .add_string_choice("Markdown", "markdown")
+                .add_string_choice("HTML", "html"),
+            )
+            .default_member_permissions(Permissions::MANAGE_ROLES)
+    }
+
+    async fn execute(
+        interaction_context: CommandInteractionContext<'_>,
+        state: &BouncerState,
+    ) -> anyhow::Result<()> {
+        let user = match interaction_context.options.get_user_and_member(0) {
+            Some((user, _)) => user,
+            None => unreachable!("The user option is required."),
+        };
+        let format = match interaction_context.options.get_string_option("format") {
+            Some("html") => Format::Html,
+            _ => Format::Markdown,
+        };
+
+        let database_user_id = database::to_id(user.id.get());
+        let interview = match interaction_context.options.get_integer_option("interview") {
+            Some(interview_id) => Interview::fetch(&state.database, interview_id)
+                .await?
+                .filter(|interview| interview.user_id == database_user_id),
+            None => Interview::latest(&state.database, database_user_id).await?,
+        };
+        let Some(interview) = interview else {
+            interaction_context
+                .reply_string(
+                    format!("No such interview of {} was found.", user.id.mention()),
+                    Some(true),
+                )
+                .await?;
+            return Ok(());
+        };
+        let rendered = transcript::render(&state.database, &interview, format).await?;
+
+        interaction_context
+            .reply(
+                CreateInteractionResponseMessage::new()
+                    .content(format!(
+                        "Transcript of the interview `{}` of {}.",
+                        interview.id,
+                        user.id.mention()
+                    ))
+                    .add_file(CreateAttachment::bytes(
+                        rendered.into_bytes(),
+                        format!("transcript-{}.{}", interview.id, format.extension()),
+                    ))
+                    .ephemeral(true),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+//! The application form users fill in before their interview.
+
+use serenity::all::{ActionRow, CreateActionRow, CreateInputText, CreateModal, InputTextStyle};
+
+use crate::bot::{
+    extensions::modal_components::ModalComponentsExt,
+    helpers::interaction_context::ModalInteractionContext, lifecycle, BouncerState,
+};
+
+pub const CUSTOM_ID: &str = "apply";
+
+const AGE_INPUT_ID: &str = "age";
+const SOURCE_INPUT_ID: &str = "source";
+const REASON_INPUT_ID: &str = "reason";
+
+/// Ages accepted on the form, Discord requires users to be at least 13.
+const AGE_RANGE: std::ops::RangeInclusive<i64> = 13..=120;
+
+/// Builds the application form.
+pub fn modal<'a>() -> CreateModal<'a> {
+    CreateModal::new(CUSTOM_ID, "Apply for an Interview").components(vec![
+        CreateActionRow::InputText(
+            CreateInputText::new(InputTextStyle::Short, "Age", AGE_INPUT_ID)
+                .min_length(1)
+                .max_length(3)
+                .required(true),
+        ),
+        CreateActionRow::InputText(
+            CreateInputText::new(
+                InputTextStyle::Paragraph,
+                "How did you find the server?",
+                SOURCE_INPUT_ID,
+            )
+            .max_length(500)
+            .required(true),
+        ),
+        CreateActionRow::InputText(
+            CreateInputText::new(
+                InputTextStyle::Paragraph,
+                "Why do you want access?",
+                REASON_INPUT_ID,
+            )
+            .max_length(1000)
+            .required(true),
+        ),
+    ])
+}
+
+pub async fn execute_modal(
+    interaction_context: ModalInteractionContext<'_>,
+    state: &BouncerState,
+) -> anyhow::Result<()> {
+    let components: &[ActionRow] = &interaction_context.interaction.data.components;
+    let input = |custom_id: &str| {
+        components
+            .get_input_text(custom_id)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+
+    let Some(age) = input(AGE_INPUT_ID).and_then(|age| age.parse::<i64>().ok()) else {
+        interaction_context
+            .reply_string("Please enter your age as a number.", Some(true))
+            .await?;
+        return Ok(());
+    };
+    if !AGE_RANGE.contains(&age) {
+        interaction_context
+            .reply_string(
+                format!(
+                    "Please enter an age between {} and {}.",
+                    AGE_RANGE.start(),
+                    AGE_RANGE.end()
+                ),
+                Some(true),
+            )
+            .await?;
+        return Ok(());
+    }
+    let (Some(source), Some(reason)) = (input(SOURCE_INPUT_ID), input(REASON_INPUT_ID)) else {
+        interaction_context
+            .reply_string("Please answer every question of the form.", Some(true))
+            .await?;
+        return Ok(());
+    };
+
+    match lifecycle::submit_application(
+        &interaction_context.context.http,
+        state,
+        interaction_context.interaction.user.id,
+        age,
+        source,
+        reason,
+    )
+    .await
+    {
+        Ok(()) => {
+            interaction_context
+                .reply_string(
+                    "Your application has been submitted, an interviewer will reach out to you soon.",
+                    Some(true),
+                )
+                .await?;
+        }
+        Err(error) if error.is_user_facing() => {
+            interaction_context.reply_string(error, Some(true)).await?;
+        }
+        Err(error) => return Err(error.into()),
+    }
+
+    Ok(())
+}
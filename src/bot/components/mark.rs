@@ -0,0 +1,298 @@
+//! Buttons attached to mark messages.
+//!
+//! Custom IDs are in the `mark:<action>:<user ID>` format, so they keep working after a
+//! restart without any state besides the database.
+
+use serenity::all::{
+    ActionRow, ButtonStyle, CreateActionRow, CreateButton, CreateInputText, CreateModal,
+    InputTextStyle, Mentionable, RoleId, UserId,
+};
+
+use crate::bot::{
+    context::Tier,
+    database::UserStatus,
+    extensions::modal_components::ModalComponentsExt,
+    helpers::interaction_context::{ComponentInteractionContext, ModalInteractionContext},
+    lifecycle, BouncerState,
+};
+
+const PREFIX: &str = "mark";
+const REASON_INPUT_ID: &str = "reason";
+
+const INTERVIEW_PREFIX: &str = "interview_";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Claim,
+    /// Starts an interview for the tier with the given name.
+    Interview(String),
+    Approve,
+    Reject,
+    /// Moves a rejected user back to pending on appeal.
+    Reopen,
+}
+
+impl Action {
+    fn name(&self) -> String {
+        match self {
+            Self::Claim => "claim".to_string(),
+            Self::Interview(tier) => format!("{INTERVIEW_PREFIX}{tier}"),
+            Self::Approve => "approve".to_string(),
+            Self::Reject => "reject".to_string(),
+            Self::Reopen => "reopen".to_string(),
+        }
+    }
+
+    pub fn custom_id(&self, user_id: UserId) -> String {
+        format!("{PREFIX}:{}:{user_id}", self.name())
+    }
+
+    /// Parses a custom ID created by [`Action::custom_id`].
+    pub fn parse(custom_id: &str) -> Option<(Self, UserId)> {
+        let mut parts = custom_id.split(':');
+        if parts.next()? != PREFIX {
+            return None;
+        }
+
+        let action = match parts.next()? {
+            "claim" => Self::Claim,
+            "approve" => Self::Approve,
+            "reject" => Self::Reject,
+            "reopen" => Self::Reopen,
+            name => Self::Interview(name.strip_prefix(INTERVIEW_PREFIX)?.to_string()),
+        };
+        let user_id = parts.next()?.parse::<u64>().ok().filter(|id| *id != 0)?;
+
+        Some((action, UserId::new(user_id)))
+    }
+}
+
+/// Buttons to show on the mark message of a user with the given status. Approved users get
+/// buttons to upgrade to tiers ranked above `approved_rank`.
+pub fn buttons<'a>(
+    tiers: &[Tier],
+    user_id: UserId,
+    status: UserStatus,
+    approved_rank: Option<u32>,
+) -> Vec<CreateActionRow<'a>> {
+    let button = |action: Action, label: String, style: ButtonStyle| {
+        CreateButton::new(action.custom_id(user_id))
+            .label(label)
+            .style(style)
+    };
+    //* A row fits 5 buttons and a message 5 rows, so at most 20 tiers get a button.
+    let interview_rows = |tiers: Vec<&Tier>, label: fn(&Tier) -> String| {
+        tiers
+            .chunks(5)
+            .take(4)
+            .map(|chunk| {
+                CreateActionRow::Buttons(
+                    chunk
+                        .iter()
+                        .map(|tier| {
+                            button(
+                                Action::Interview(tier.name.clone()),
+                                label(tier),
+                                ButtonStyle::Primary,
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .into(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    match status {
+        UserStatus::Pending => {
+            let mut rows = vec![CreateActionRow::Buttons(
+                vec![
+                    button(Action::Claim, "Claim".to_string(), ButtonStyle::Secondary),
+                    button(Action::Reject, "Reject".to_string(), ButtonStyle::Danger),
+                ]
+                .into(),
+            )];
+            rows.extend(interview_rows(tiers.iter().collect(), |tier| {
+                format!("Start {} Interview", tier.label)
+            }));
+
+            rows
+        }
+        UserStatus::Ongoing => vec![CreateActionRow::Buttons(
+            vec![
+                button(Action::Approve, "Approve".to_string(), ButtonStyle::Success),
+                button(Action::Reject, "Reject".to_string(), ButtonStyle::Danger),
+            ]
+            .into(),
+        )],
+        UserStatus::Approved => interview_rows(
+            tiers
+                .iter()
+                .filter(|tier| !approved_rank.is_some_and(|rank| tier.rank <= rank))
+                .collect(),
+            |tier| format!("Upgrade to {}", tier.label),
+        ),
+        UserStatus::Rejected => vec![CreateActionRow::Buttons(
+            vec![button(
+                Action::Reopen,
+                "Reopen".to_string(),
+                ButtonStyle::Secondary,
+            )]
+            .into(),
+        )],
+    }
+}
+
+pub async fn execute(
+    interaction_context: ComponentInteractionContext<'_>,
+    state: &BouncerState,
+    action: Action,
+    user_id: UserId,
+) -> anyhow::Result<()> {
+    let interviewer_role_ids: &[RoleId] = match &interaction_context.interaction.member {
+        Some(member) => &member.roles,
+        None => &[],
+    };
+    if !state.context.roles.is_interviewer(interviewer_role_ids) {
+        interaction_context
+            .reply_string("Only interviewers can use these buttons.", Some(true))
+            .await?;
+        return Ok(());
+    }
+
+    let http = &interaction_context.context.http;
+    let interviewer_id = interaction_context.interaction.user.id;
+    if user_id == interviewer_id {
+        interaction_context
+            .reply_string("You cannot interview yourself.", Some(true))
+            .await?;
+        return Ok(());
+    }
+
+    if action == Action::Reject {
+        interaction_context
+            .reply_modal(
+                CreateModal::new(action.custom_id(user_id), "Reject User").components(vec![
+                    CreateActionRow::InputText(
+                        CreateInputText::new(InputTextStyle::Paragraph, "Reason", REASON_INPUT_ID)
+                            .required(true)
+                            .max_length(1000),
+                    ),
+                ]),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    //* Swapping roles and opening or closing channels can take longer than Discord waits for
+    //* a response.
+    interaction_context.defer_reply(Some(true)).await?;
+
+    let result = match &action {
+        Action::Claim => lifecycle::claim_user(http, state, user_id, interviewer_id)
+            .await
+            .map(|()| format!("You have claimed {}.", user_id.mention())),
+        Action::Interview(tier_name) => {
+            match lifecycle::resolve_tier(state, tier_name, interviewer_role_ids) {
+                Ok(tier) => lifecycle::start_interview(http, state, user_id, interviewer_id, tier)
+                    .await
+                    .map(|channel_id| {
+                        format!(
+                            "{} is now being interviewed by you (`{}`) in {}.",
+                            user_id.mention(),
+                            tier.label,
+                            channel_id.mention()
+                        )
+                    }),
+                Err(error) => Err(error),
+            }
+        }
+        Action::Approve => {
+            lifecycle::approve_interview(http, state, user_id, interviewer_id, interviewer_role_ids)
+                .await
+                .map(|tier_label| {
+                    format!("{} has been approved (`{tier_label}`).", user_id.mention())
+                })
+        }
+        Action::Reopen => lifecycle::reopen_user(http, state, user_id, interviewer_id)
+            .await
+            .map(|()| format!("{} can be interviewed again.", user_id.mention())),
+        Action::Reject => unreachable!("Rejections are asked for a reason first."),
+    };
+
+    match result {
+        Ok(message) => interaction_context.edit_reply_string(message).await?,
+        Err(error) if error.is_user_facing() => {
+            interaction_context.edit_reply_string(error).await?;
+        }
+        Err(error) => {
+            interaction_context
+                .edit_reply_string("An unexpected error occurred, please try again.")
+                .await?;
+            return Err(error.into());
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn execute_modal(
+    interaction_context: ModalInteractionContext<'_>,
+    state: &BouncerState,
+    action: Action,
+    user_id: UserId,
+) -> anyhow::Result<()> {
+    if action != Action::Reject {
+        return Ok(());
+    }
+    let interviewer_role_ids: &[RoleId] = match &interaction_context.interaction.member {
+        Some(member) => &member.roles,
+        None => &[],
+    };
+    if !state.context.roles.is_interviewer(interviewer_role_ids) {
+        interaction_context
+            .reply_string("Only interviewers can reject users.", Some(true))
+            .await?;
+        return Ok(());
+    }
+
+    let components: &[ActionRow] = &interaction_context.interaction.data.components;
+    let reason = match components.get_input_text(REASON_INPUT_ID) {
+        Some(reason) if !reason.trim().is_empty() => reason.trim(),
+        _ => {
+            interaction_context
+                .reply_string("Please enter a reason for the rejection.", Some(true))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    interaction_context.defer_reply(Some(true)).await?;
+    match lifecycle::reject_interview(
+        &interaction_context.context.http,
+        state,
+        user_id,
+        interaction_context.interaction.user.id,
+        interviewer_role_ids,
+        reason,
+    )
+    .await
+    {
+        Ok(()) => {
+            interaction_context
+                .edit_reply_string(format!("{} has been rejected: {reason}", user_id.mention()))
+                .await?;
+        }
+        Err(error) if error.is_user_facing() => {
+            interaction_context.edit_reply_string(error).await?;
+        }
+        Err(error) => {
+            interaction_context
+                .edit_reply_string("An unexpected error occurred, please try again.")
+                .await?;
+            return Err(error.into());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+use tracing::trace;
+
+use super::{
+    helpers::interaction_context::{ComponentInteractionContext, ModalInteractionContext},
+    BouncerState,
+};
+
+pub mod apply;
+pub mod mark;
+pub mod queue;
+
+pub async fn run_component(
+    interaction_context: ComponentInteractionContext<'_>,
+    state: Arc<RwLock<BouncerState>>,
+) -> anyhow::Result<()> {
+    let custom_id = interaction_context.interaction.data.custom_id.as_str();
+
+    trace!("running the `{custom_id}` component...");
+    let component_result = if let Some((action, user_id)) = mark::Action::parse(custom_id) {
+        mark::execute(interaction_context, &*state.read().await, action, user_id).await
+    } else if let Some(page) = queue::parse(custom_id) {
+        queue::execute(interaction_context, &*state.read().await, page).await
+    } else {
+        Ok(())
+    };
+
+    if component_result.is_ok() {
+        trace!("ran the `{custom_id}` component");
+    }
+
+    component_result
+}
+
+pub async fn run_modal(
+    interaction_context: ModalInteractionContext<'_>,
+    state: Arc<RwLock<BouncerState>>,
+) -> anyhow::Result<()> {
+    let custom_id = interaction_context.interaction.data.custom_id.as_str();
+
+    trace!("running the `{custom_id}` modal...");
+    let modal_result = if let Some((action, user_id)) = mark::Action::parse(custom_id) {
+        mark::execute_modal(interaction_context, &*state.read().await, action, user_id).await
+    } else if custom_id == apply::CUSTOM_ID {
+        apply::execute_modal(interaction_context, &*state.read().await).await
+    } else {
+        Ok(())
+    };
+
+    if modal_result.is_ok() {
+        trace!("ran the `{custom_id}` modal");
+    }
+
+    modal_result
+}
@@ -0,0 +1,101 @@
+//! Paging buttons of the interview queue.
+//!
+//! Custom IDs are in the `queue:<previous|next>:<page>` format, where the page is the one
+//! the button leads to.
+
+use serenity::all::{
+    ButtonStyle, Colour, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter,
+    CreateInteractionResponseMessage,
+};
+
+use crate::bot::{
+    database::User, helpers::interaction_context::ComponentInteractionContext, BouncerState,
+};
+
+const PREFIX: &str = "queue";
+const PAGE_SIZE: i64 = 10;
+
+/// Parses a custom ID created by [`page`] into the page it leads to.
+pub fn parse(custom_id: &str) -> Option<i64> {
+    let mut parts = custom_id.split(':');
+    if parts.next()? != PREFIX {
+        return None;
+    }
+
+    match parts.next()? {
+        "previous" | "next" => parts.next()?.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Builds a page of the queue of users waiting for an interview.
+pub async fn page<'a>(
+    state: &BouncerState,
+    page: i64,
+) -> anyhow::Result<CreateInteractionResponseMessage<'a>> {
+    let total = User::count_pending(&state.database).await?;
+    let last_page = (total - 1).max(0) / PAGE_SIZE;
+    let page = page.clamp(0, last_page);
+
+    let users = User::fetch_pending(&state.database, PAGE_SIZE, page * PAGE_SIZE).await?;
+
+    let description = if users.is_empty() {
+        "Nobody is waiting for an interview.".to_string()
+    } else {
+        users
+            .iter()
+            .enumerate()
+            .map(|(index, user)| {
+                let position = page * PAGE_SIZE + i64::try_from(index).unwrap_or_default() + 1;
+                let claim = user.claimed_by.map_or_else(
+                    || "unclaimed".to_string(),
+                    |claimed_by| format!("claimed by <@{claimed_by}>"),
+                );
+
+                format!(
+                    "{position}. <@{}>, waiting since <t:{}:R>, {claim}",
+                    user.user_id,
+                    user.mark_date.and_utc().timestamp()
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+
+    let embed = CreateEmbed::new()
+        .title("Interview Queue")
+        .colour(Colour::ORANGE)
+        .description(description)
+        .footer(CreateEmbedFooter::new(format!(
+            "Page {} of {}, {total} waiting",
+            page + 1,
+            last_page + 1
+        )));
+
+    let buttons = vec![
+        CreateButton::new(format!("{PREFIX}:previous:{}", page - 1))
+            .label("Previous")
+            .style(ButtonStyle::Secondary)
+            .disabled(page == 0),
+        CreateButton::new(format!("{PREFIX}:next:{}", page + 1))
+            .label("Next")
+            .style(ButtonStyle::Secondary)
+            .disabled(page >= last_page),
+    ];
+
+    Ok(CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(vec![CreateActionRow::Buttons(buttons.into())]))
+}
+
+pub async fn execute(
+    interaction_context: ComponentInteractionContext<'_>,
+    state: &BouncerState,
+    page_number: i64,
+) -> anyhow::Result<()> {
+    interaction_context
+        .update_message(page(state, page_number).await?)
+        .await?;
+
+    Ok(())
+}
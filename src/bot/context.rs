@@ -1,4 +1,4 @@
-use serenity::all::{Context, Guild, GuildChannel, Role};
+use serenity::all::{ChannelType, Context, Guild, GuildChannel, Role, RoleId};
 use tracing::trace;
 
 use crate::config;
@@ -10,11 +10,14 @@ pub struct BouncerContext {
     pub guild: Guild,
     pub channels: Channels,
     pub roles: Roles,
+    /// Verification tiers, lowest rank first.
+    pub tiers: Vec<Tier>,
 }
 
 #[derive(Debug, Default)]
 pub struct Channels {
     pub interview_marks: GuildChannel,
+    pub interviews_category: GuildChannel,
 }
 
 #[derive(Debug, Default)]
@@ -24,8 +27,39 @@ pub struct Roles {
     pub pending_interview: Role,
     pub ongoing_interview: Role,
 
-    pub text_verified: Role,
-    pub id_verified: Role,
+    pub rejected: Option<Role>,
+}
+
+impl Roles {
+    /// Whether any of the given roles is an interviewer role.
+    pub fn is_interviewer(&self, role_ids: &[RoleId]) -> bool {
+        self.interviewers
+            .iter()
+            .any(|role| role_ids.contains(&role.id))
+    }
+}
+
+#[derive(Debug)]
+pub struct Tier {
+    pub name: String,
+    pub label: String,
+    pub role: Role,
+    pub interviewer_roles: Vec<Role>,
+    pub rank: u32,
+    /// Questions asked when an interview for the tier starts, in order.
+    pub questions: Vec<String>,
+    pub id_verification: bool,
+}
+
+impl Tier {
+    /// Whether an interviewer with the given roles can interview for the tier.
+    pub fn can_interview(&self, role_ids: &[RoleId]) -> bool {
+        self.interviewer_roles.is_empty()
+            || self
+                .interviewer_roles
+                .iter()
+                .any(|role| role_ids.contains(&role.id))
+    }
 }
 
 impl BouncerContext {
@@ -62,6 +96,25 @@ impl BouncerContext {
             }
         };
 
+        let interviews_category = match guild
+            .channels
+            .get(&discord_config.channels.interviews_category_id.into())
+        {
+            Some(channel) if channel.kind == ChannelType::Category => channel,
+            Some(_) => {
+                anyhow::bail!(
+                    "channel for `interviews_category_id` with the id `{}` is not a category",
+                    discord_config.channels.interviews_category_id
+                );
+            }
+            None => {
+                anyhow::bail!(
+                    "channel for `interviews_category_id` with the id `{}` could not be found",
+                    discord_config.channels.interviews_category_id
+                );
+            }
+        };
+
         let interviewer_roles: Vec<&Role> = discord_config
             .roles
             .interviewer_ids
@@ -102,27 +155,21 @@ impl BouncerContext {
             }
         };
 
-        let text_verified_role = match guild
-            .roles
-            .get(&discord_config.roles.text_verified_id.into())
-        {
-            Some(role) => role,
-            None => {
-                anyhow::bail!(
-                    "role for `text_verified_id` with the id `{}` could not be found",
-                    discord_config.roles.text_verified_id
-                );
-            }
-        };
+        let tiers = Self::try_populate_tiers(&guild, discord_config)?;
 
-        let id_verified_role = match guild.roles.get(&discord_config.roles.id_verified_id.into()) {
-            Some(role) => role,
-            None => {
-                anyhow::bail!(
-                    "role for `id_verified_id` with the id `{}` could not be found",
-                    discord_config.roles.id_verified_id
-                );
+        let rejected_role = match discord_config.roles.rejected_id {
+            Some(rejected_id) => match guild.roles.get(&rejected_id.into()) {
+                Some(role) => Some(role),
+                None => {
+                    anyhow::bail!(
+                        "role for `rejected_id` with the id `{rejected_id}` could not be found"
+                    );
+                }
+            },
+            None if discord_config.interviews.rejected_rejoin == config::RejectedRejoin::Role => {
+                anyhow::bail!("`rejected_rejoin` is set to `role`, but `rejected_id` is not set");
             }
+            None => None,
         };
 
         trace!("populated the context");
@@ -132,17 +179,121 @@ impl BouncerContext {
             guild: guild.to_owned(),
             channels: Channels {
                 interview_marks: interview_marks_channel.to_owned(),
+                interviews_category: interviews_category.to_owned(),
             },
             roles: Roles {
                 interviewers: interviewer_roles.into_iter().cloned().collect(),
                 pending_interview: pending_interview_role.to_owned(),
                 ongoing_interview: ongoing_interview_role.to_owned(),
-                text_verified: text_verified_role.to_owned(),
-                id_verified: id_verified_role.to_owned(),
+                rejected: rejected_role.cloned(),
             },
+            tiers,
         })
     }
 
+    fn try_populate_tiers(
+        guild: &Guild,
+        discord_config: &config::Discord,
+    ) -> anyhow::Result<Vec<Tier>> {
+        if discord_config.tiers.is_empty() {
+            anyhow::bail!("at least one tier is required in `tiers`");
+        }
+        //* Tiers are offered as command choices, which are limited to 25.
+        if discord_config.tiers.len() > 25 {
+            anyhow::bail!("at most 25 tiers can be set in `tiers`");
+        }
+
+        let mut tiers: Vec<Tier> = Vec::with_capacity(discord_config.tiers.len());
+        for tier in &discord_config.tiers {
+            let is_valid_name = !tier.name.is_empty()
+                && tier.name.len() <= 32
+                && tier.name.chars().all(|character| {
+                    character.is_ascii_lowercase()
+                        || character.is_ascii_digit()
+                        || character == '_'
+                        || character == '-'
+                });
+            if !is_valid_name {
+                anyhow::bail!(
+                    "tier name `{}` must be 1 to 32 lowercase letters, digits, `_` or `-`",
+                    tier.name
+                );
+            }
+            if tiers.iter().any(|other| other.name == tier.name) {
+                anyhow::bail!("tier name `{}` is used more than once", tier.name);
+            }
+            //* Labels are shown on buttons, which fit 80 characters.
+            if tier
+                .label
+                .as_ref()
+                .is_some_and(|label| label.trim().is_empty() || label.chars().count() > 80)
+            {
+                anyhow::bail!(
+                    "label of the `{}` tier must be 1 to 80 characters",
+                    tier.name
+                );
+            }
+
+            let Some(role) = guild.roles.get(&tier.role_id.into()) else {
+                anyhow::bail!(
+                    "role for the `{}` tier with the id `{}` could not be found",
+                    tier.name,
+                    tier.role_id
+                );
+            };
+
+            //* Answers are summarised with a field per question, and embeds fit 25 fields with
+            //* names of up to 256 characters.
+            if tier.questions.len() > 25 {
+                anyhow::bail!(
+                    "at most 25 questions can be set for the `{}` tier",
+                    tier.name
+                );
+            }
+            if tier
+                .questions
+                .iter()
+                .any(|question| question.trim().is_empty() || question.chars().count() > 256)
+            {
+                anyhow::bail!(
+                    "questions of the `{}` tier must be 1 to 256 characters",
+                    tier.name
+                );
+            }
+
+            let mut interviewer_roles = Vec::with_capacity(tier.interviewer_role_ids.len());
+            for role_id in &tier.interviewer_role_ids {
+                match guild.roles.get(&(*role_id).into()) {
+                    Some(role) => interviewer_roles.push(role.to_owned()),
+                    None => {
+                        anyhow::bail!(
+                            "interviewer role for the `{}` tier with the id `{role_id}` could not be found",
+                            tier.name
+                        );
+                    }
+                }
+            }
+
+            tiers.push(Tier {
+                name: tier.name.clone(),
+                label: tier.label.clone().unwrap_or_else(|| tier.name.clone()),
+                role: role.to_owned(),
+                interviewer_roles,
+                rank: tier.rank,
+                questions: tier.questions.clone(),
+                id_verification: tier.id_verification,
+            });
+        }
+        tiers.sort_by_key(|tier| tier.rank);
+
+        Ok(tiers)
+    }
+
+    /// Finds a configured tier by its name.
+    pub fn tier(&self, name: &str) -> Option<&Tier> {
+        self.tiers.iter().find(|tier| tier.name == name)
+    }
+
     pub const fn is_populated(&self) -> bool {
         self.is_populated
     }
@@ -1,7 +1,19 @@
 use chrono::NaiveDateTime;
-use sqlx::{FromRow, Type};
+use sqlx::{FromRow, SqliteExecutor, Type};
 
-#[derive(Type, Debug, Default)]
+use super::status::Transition;
+
+/// Converts a Discord snowflake into the signed integer `SQLite` stores it as.
+pub fn to_id(id: u64) -> i64 {
+    i64::try_from(id).expect("failed to convert snowflake from u64 to i64")
+}
+
+/// Converts a stored ID back into a Discord snowflake.
+pub fn from_id(id: i64) -> u64 {
+    u64::try_from(id).expect("failed to convert snowflake from i64 to u64")
+}
+
+#[derive(Type, Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[sqlx(rename_all = "lowercase")]
 pub enum UserStatus {
     #[default]
@@ -12,55 +24,996 @@ pub enum UserStatus {
     Rejected,
 }
 
-impl From<String> for UserStatus {
-    fn from(value: String) -> Self {
-        match value.as_str() {
-            "pending" => Self::Pending,
-            "ongoing" => Self::Ongoing,
-            "approved" => Self::Approved,
-            "rejected" => Self::Rejected,
-            _ => unreachable!(),
-        }
+#[derive(FromRow, Debug)]
+pub struct User {
+    pub user_id: i64,
+
+    pub status: UserStatus,
+
+    #[sqlx(default)]
+    pub mark_date: NaiveDateTime,
+
+    #[sqlx(default)]
+    pub decided_by: Option<i64>,
+    #[sqlx(default)]
+    pub decision_date: Option<NaiveDateTime>,
+    #[sqlx(default)]
+    pub decision_reason: Option<String>,
+
+    #[sqlx(default)]
+    pub mark_message_id: Option<i64>,
+    #[sqlx(default)]
+    pub offending_channel_id: Option<i64>,
+    #[sqlx(default)]
+    pub offending_message_id: Option<i64>,
+
+    #[sqlx(default)]
+    pub claimed_by: Option<i64>,
+    #[sqlx(default)]
+    pub claim_date: Option<NaiveDateTime>,
+
+    #[sqlx(default)]
+    pub last_offense_date: Option<NaiveDateTime>,
+}
+
+impl User {
+    /// Fetches the record of a user, if they have one.
+    pub async fn fetch(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+    ) -> sqlx::Result<Option<Self>> {
+        sqlx::query_as!(
+            Self,
+            r#"SELECT user_id, status AS "status: UserStatus", mark_date, decided_by,
+                    decision_date, decision_reason, mark_message_id, offending_channel_id,
+                    offending_message_id, claimed_by, claim_date, last_offense_date
+                FROM users WHERE user_id = ?"#,
+            user_id
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Fetches a page of users waiting for an interview, oldest mark first.
+    pub async fn fetch_pending(
+        executor: impl SqliteExecutor<'_>,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as!(
+            Self,
+            r#"SELECT user_id, status AS "status: UserStatus", mark_date, decided_by,
+                    decision_date, decision_reason, mark_message_id, offending_channel_id,
+                    offending_message_id, claimed_by, claim_date, last_offense_date
+                FROM users WHERE status = ?
+                ORDER BY mark_date ASC, user_id ASC LIMIT ? OFFSET ?"#,
+            UserStatus::Pending,
+            limit,
+            offset
+        )
+        .fetch_all(executor)
+        .await
+    }
+
+    /// Fetches the status of every user.
+    pub async fn fetch_statuses(
+        executor: impl SqliteExecutor<'_>,
+    ) -> sqlx::Result<Vec<(i64, UserStatus)>> {
+        let rows = sqlx::query!(r#"SELECT user_id, status AS "status: UserStatus" FROM users"#)
+            .fetch_all(executor)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.user_id, row.status))
+            .collect())
+    }
+
+    /// Counts the users waiting for an interview.
+    pub async fn count_pending(executor: impl SqliteExecutor<'_>) -> sqlx::Result<i64> {
+        sqlx::query_scalar!(
+            "SELECT COUNT(*) FROM users WHERE status = ?",
+            UserStatus::Pending
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    /// Marks a user as pending an interview, optionally because of the given message.
+    /// Returns whether the user was marked, which isn't the case if they have a record already.
+    ///
+    /// The transition is expected to start from no record, like [`super::status::MARK`].
+    pub async fn mark(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+        transition: Transition,
+        channel_id: Option<i64>,
+        message_id: Option<i64>,
+    ) -> sqlx::Result<bool> {
+        let status = transition.to();
+        //* Marking a user for a message reports it, unlike marking them when they join.
+        let result = sqlx::query!(
+            "INSERT INTO users(user_id, status, offending_channel_id, offending_message_id,
+                    last_offense_date)
+                VALUES(?, ?, ?, ?, CASE WHEN ? IS NULL THEN NULL ELSE DATETIME('now') END)
+                ON CONFLICT(user_id) DO NOTHING",
+            user_id,
+            status,
+            channel_id,
+            message_id,
+            message_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Updates the last time a post of a marked user in a watched channel was reported,
+    /// unless one was reported in the last `interval_minutes`. Returns whether it was updated.
+    pub async fn refresh_mark(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+        interval_minutes: u64,
+    ) -> sqlx::Result<bool> {
+        let interval = format!("-{interval_minutes} minutes");
+        let result = sqlx::query!(
+            "UPDATE users SET last_offense_date = DATETIME('now')
+                WHERE user_id = ?
+                    AND (last_offense_date IS NULL OR last_offense_date <= DATETIME('now', ?))",
+            user_id,
+            interval
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Sets the mark message of a user in the interview marks channel.
+    pub async fn set_mark_message_id(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+        message_id: i64,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE users SET mark_message_id = ? WHERE user_id = ?",
+            message_id,
+            user_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Claims a pending user for an interviewer, unless another interviewer claimed them
+    /// already. Returns whether they were claimed.
+    pub async fn claim(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+        claimed_by: i64,
+    ) -> sqlx::Result<bool> {
+        let result = sqlx::query!(
+            "UPDATE users SET claimed_by = ?, claim_date = DATETIME('now')
+                WHERE user_id = ? AND status = ? AND (claimed_by IS NULL OR claimed_by = ?)",
+            claimed_by,
+            user_id,
+            UserStatus::Pending,
+            claimed_by
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Changes the status of a user, creating their record if it doesn't exist yet.
+    ///
+    /// Claims are dropped, since they only hold for the next interview of a pending user.
+    pub async fn set_status(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+        transition: Transition,
+    ) -> sqlx::Result<()> {
+        let status = transition.to();
+        sqlx::query!(
+            "INSERT INTO users(user_id, status) VALUES(?, ?)
+                ON CONFLICT(user_id) DO UPDATE SET
+                    status = excluded.status,
+                    claimed_by = NULL,
+                    claim_date = NULL",
+            user_id,
+            status
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records the final decision about a user along with who made it and why, creating
+    /// their record if it doesn't exist yet. Claims are dropped like with `set_status`.
+    pub async fn decide(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+        transition: Transition,
+        decided_by: i64,
+        reason: Option<&str>,
+    ) -> sqlx::Result<()> {
+        let status = transition.to();
+        sqlx::query!(
+            "INSERT INTO users(user_id, status, decided_by, decision_date, decision_reason)
+                VALUES(?, ?, ?, DATETIME('now'), ?)
+                ON CONFLICT(user_id) DO UPDATE SET
+                    status = excluded.status,
+                    decided_by = excluded.decided_by,
+                    decision_date = excluded.decision_date,
+                    decision_reason = excluded.decision_reason,
+                    claimed_by = NULL,
+                    claim_date = NULL",
+            user_id,
+            status,
+            decided_by,
+            reason
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
     }
 }
 
-#[derive(Type, Debug, Default)]
+#[derive(Type, Debug, Clone, Copy, PartialEq, Eq)]
 #[sqlx(rename_all = "lowercase")]
-pub enum InterviewType {
-    #[default]
+pub enum InterviewOutcome {
+    Approved,
+    Rejected,
+    /// The interview was abandoned, like when it went stale.
+    Reverted,
+}
+
+#[derive(FromRow, Debug)]
+pub struct Interview {
+    pub id: i64,
+
+    pub user_id: i64,
+    pub interviewer_id: i64,
+
+    pub tier: String,
+    /// The tier the user was approved for when the interview started, if it's an upgrade.
+    #[sqlx(default)]
+    pub previous_tier: Option<String>,
+
+    /// How the interview ended, none while it's ongoing.
     #[sqlx(default)]
-    Text,
-    ID,
+    pub outcome: Option<InterviewOutcome>,
+
+    #[sqlx(default)]
+    pub start_date: NaiveDateTime,
+    #[sqlx(default)]
+    pub end_date: Option<NaiveDateTime>,
+    /// Why the interview ended the way it did.
+    #[sqlx(default)]
+    pub reason: Option<String>,
+    /// Free-form notes about the interview. The bot doesn't write any, the column is kept
+    /// for notes added to the records by hand.
+    #[sqlx(default)]
+    pub notes: Option<String>,
+
+    #[sqlx(default)]
+    pub channel_id: Option<i64>,
 }
 
-impl std::fmt::Display for InterviewType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            &InterviewType::Text => write!(f, "text"),
-            &InterviewType::ID => write!(f, "id"),
-        }
+impl Interview {
+    /// Records a new interview of a user by an interviewer, returning its ID.
+    pub async fn insert(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+        interviewer_id: i64,
+        tier: &str,
+        previous_tier: Option<&str>,
+    ) -> sqlx::Result<i64> {
+        let result = sqlx::query!(
+            "INSERT INTO interviews(user_id, interviewer_id, tier, previous_tier)
+                VALUES(?, ?, ?, ?)",
+            user_id,
+            interviewer_id,
+            tier,
+            previous_tier
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Fetches an interview by its ID.
+    pub async fn fetch(executor: impl SqliteExecutor<'_>, id: i64) -> sqlx::Result<Option<Self>> {
+        sqlx::query_as!(
+            Self,
+            r#"SELECT id, user_id, interviewer_id, tier, previous_tier,
+                    outcome AS "outcome: InterviewOutcome", start_date, end_date, reason, notes,
+                    channel_id
+                FROM interviews WHERE id = ?"#,
+            id
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Fetches the most recent interview of a user.
+    pub async fn latest(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+    ) -> sqlx::Result<Option<Self>> {
+        sqlx::query_as!(
+            Self,
+            r#"SELECT id, user_id, interviewer_id, tier, previous_tier,
+                    outcome AS "outcome: InterviewOutcome", start_date, end_date, reason, notes,
+                    channel_id
+                FROM interviews WHERE user_id = ?
+                ORDER BY start_date DESC, id DESC LIMIT 1"#,
+            user_id
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Fetches the most recent approved interview of a user, which decides their tier.
+    pub async fn latest_approved(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+    ) -> sqlx::Result<Option<Self>> {
+        sqlx::query_as!(
+            Self,
+            r#"SELECT id, user_id, interviewer_id, tier, previous_tier,
+                    outcome AS "outcome: InterviewOutcome", start_date, end_date, reason, notes,
+                    channel_id
+                FROM interviews WHERE user_id = ? AND outcome = ?
+                ORDER BY start_date DESC, id DESC LIMIT 1"#,
+            user_id,
+            InterviewOutcome::Approved
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Fetches every interview of a user, oldest first.
+    pub async fn fetch_all(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+    ) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as!(
+            Self,
+            r#"SELECT id, user_id, interviewer_id, tier, previous_tier,
+                    outcome AS "outcome: InterviewOutcome", start_date, end_date, reason, notes,
+                    channel_id
+                FROM interviews WHERE user_id = ?
+                ORDER BY start_date ASC, id ASC"#,
+            user_id
+        )
+        .fetch_all(executor)
+        .await
+    }
+
+    /// Fetches the unfinished interviews that started more than the given number of
+    /// minutes ago.
+    pub async fn fetch_stale(
+        executor: impl SqliteExecutor<'_>,
+        stale_after_minutes: i64,
+    ) -> sqlx::Result<Vec<Self>> {
+        let stale_after = format!("-{stale_after_minutes} minutes");
+        sqlx::query_as!(
+            Self,
+            r#"SELECT id, user_id, interviewer_id, tier, previous_tier,
+                    outcome AS "outcome: InterviewOutcome", start_date, end_date, reason, notes,
+                    channel_id
+                FROM interviews
+                WHERE outcome IS NULL AND start_date < DATETIME('now', ?)"#,
+            stale_after
+        )
+        .fetch_all(executor)
+        .await
+    }
+
+    /// Fetches the ongoing interview taking place in a channel.
+    pub async fn fetch_ongoing_by_channel(
+        executor: impl SqliteExecutor<'_>,
+        channel_id: i64,
+    ) -> sqlx::Result<Option<Self>> {
+        sqlx::query_as!(
+            Self,
+            r#"SELECT id, user_id, interviewer_id, tier, previous_tier,
+                    outcome AS "outcome: InterviewOutcome", start_date, end_date, reason, notes,
+                    channel_id
+                FROM interviews WHERE channel_id = ? AND outcome IS NULL"#,
+            channel_id
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Sets the private channel an interview takes place in.
+    pub async fn set_channel_id(
+        executor: impl SqliteExecutor<'_>,
+        id: i64,
+        channel_id: i64,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE interviews SET channel_id = ? WHERE id = ?",
+            channel_id,
+            id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records how and why an interview ended.
+    pub async fn finish(
+        executor: impl SqliteExecutor<'_>,
+        id: i64,
+        outcome: InterviewOutcome,
+        reason: Option<&str>,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE interviews SET outcome = ?, end_date = DATETIME('now'), reason = ?
+                WHERE id = ?",
+            outcome,
+            reason,
+            id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fetches the tier of the latest approved interview of every user who has one.
+    pub async fn fetch_approved_tiers(
+        executor: impl SqliteExecutor<'_>,
+    ) -> sqlx::Result<Vec<(i64, String)>> {
+        let rows = sqlx::query!(
+            "SELECT user_id, tier FROM interviews
+                WHERE outcome = ? AND id = (
+                    SELECT latest.id FROM interviews AS latest
+                        WHERE latest.user_id = interviews.user_id AND latest.outcome = ?
+                        ORDER BY latest.start_date DESC, latest.id DESC LIMIT 1
+                )",
+            InterviewOutcome::Approved,
+            InterviewOutcome::Approved
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.user_id, row.tier))
+            .collect())
+    }
+
+    /// Fetches every tier name stored with interviews, previous tiers included.
+    pub async fn fetch_tier_names(executor: impl SqliteExecutor<'_>) -> sqlx::Result<Vec<String>> {
+        sqlx::query_scalar!(
+            r#"SELECT tier AS "tier!" FROM interviews
+                UNION SELECT previous_tier FROM interviews WHERE previous_tier IS NOT NULL"#
+        )
+        .fetch_all(executor)
+        .await
+    }
+
+    /// Renames a tier stored with interviews, previous tiers included. Returns how many
+    /// interviews were renamed.
+    pub async fn rename_tier(
+        executor: impl SqliteExecutor<'_>,
+        from: &str,
+        to: &str,
+    ) -> sqlx::Result<u64> {
+        Ok(sqlx::query!(
+            "UPDATE interviews
+                SET tier = CASE WHEN tier = ?1 THEN ?2 ELSE tier END,
+                    previous_tier = CASE WHEN previous_tier = ?1 THEN ?2 ELSE previous_tier END
+                WHERE tier = ?1 OR previous_tier = ?1",
+            from,
+            to
+        )
+        .execute(executor)
+        .await?
+        .rows_affected())
     }
 }
 
+/// The answer of an interviewee to a question of their tier.
 #[derive(FromRow, Debug)]
-pub struct User {
+pub struct InterviewAnswer {
+    pub interview_id: i64,
+    /// Index of the question among the questions of the tier, starting from 0.
+    pub position: i64,
+
+    pub question: String,
+    pub answer: String,
+
+    #[sqlx(default)]
+    pub answer_date: NaiveDateTime,
+}
+
+impl InterviewAnswer {
+    /// Records the answer to the question at the given position. Returns whether it was
+    /// recorded, which isn't the case if the question was answered already.
+    pub async fn insert(
+        executor: impl SqliteExecutor<'_>,
+        interview_id: i64,
+        position: i64,
+        question: &str,
+        answer: &str,
+    ) -> sqlx::Result<bool> {
+        let result = sqlx::query!(
+            "INSERT INTO interview_answers(interview_id, position, question, answer)
+                VALUES(?, ?, ?, ?)
+                ON CONFLICT(interview_id, position) DO NOTHING",
+            interview_id,
+            position,
+            question,
+            answer
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Fetches the answers of an interview, in the order of the questions.
+    pub async fn fetch_all(
+        executor: impl SqliteExecutor<'_>,
+        interview_id: i64,
+    ) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as!(
+            Self,
+            "SELECT interview_id, position, question, answer, answer_date
+                FROM interview_answers WHERE interview_id = ? ORDER BY position ASC",
+            interview_id
+        )
+        .fetch_all(executor)
+        .await
+    }
+
+    /// Counts the answered questions of an interview.
+    pub async fn count(executor: impl SqliteExecutor<'_>, interview_id: i64) -> sqlx::Result<i64> {
+        sqlx::query_scalar!(
+            "SELECT COUNT(*) FROM interview_answers WHERE interview_id = ?",
+            interview_id
+        )
+        .fetch_one(executor)
+        .await
+    }
+}
+
+/// A message posted in the channel of an interview.
+#[derive(FromRow, Debug)]
+pub struct TranscriptMessage {
+    pub interview_id: i64,
+    pub message_id: i64,
+
+    pub author_id: i64,
+    pub author_name: String,
+
+    pub content: String,
+    /// File names of the attachments, one per line.
+    #[sqlx(default)]
+    pub attachments: Option<String>,
+
+    pub message_date: NaiveDateTime,
+}
+
+impl TranscriptMessage {
+    /// Records a message of an interview, replacing it if it was captured before.
+    pub async fn upsert(executor: impl SqliteExecutor<'_>, message: &Self) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO transcript_messages(interview_id, message_id, author_id, author_name,
+                    content, attachments, message_date)
+                VALUES(?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(interview_id, message_id) DO UPDATE SET
+                    author_name = excluded.author_name,
+                    content = excluded.content,
+                    attachments = excluded.attachments",
+            message.interview_id,
+            message.message_id,
+            message.author_id,
+            message.author_name,
+            message.content,
+            message.attachments,
+            message.message_date
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fetches the messages of an interview, oldest first.
+    pub async fn fetch_all(
+        executor: impl SqliteExecutor<'_>,
+        interview_id: i64,
+    ) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as!(
+            Self,
+            "SELECT interview_id, message_id, author_id, author_name, content, attachments,
+                    message_date
+                FROM transcript_messages WHERE interview_id = ? ORDER BY message_id ASC",
+            interview_id
+        )
+        .fetch_all(executor)
+        .await
+    }
+}
+
+/// The hash and metadata of an attachment posted during an ID verification interview, kept
+/// in place of the deleted attachment.
+#[derive(FromRow, Debug)]
+pub struct AttachmentHash {
+    pub id: i64,
+
+    pub interview_id: i64,
     pub user_id: i64,
 
-    pub status: UserStatus,
+    /// Hexadecimal SHA-256 hash of the attachment.
+    pub sha256: String,
+    /// Size of the attachment in bytes.
+    pub size: i64,
+    #[sqlx(default)]
+    pub content_type: Option<String>,
 
+    pub posted_date: NaiveDateTime,
     #[sqlx(default)]
-    pub mark_date: NaiveDateTime,
+    pub purge_date: NaiveDateTime,
 }
 
+impl AttachmentHash {
+    /// Records the hash of a deleted attachment.
+    pub async fn insert(
+        executor: impl SqliteExecutor<'_>,
+        interview_id: i64,
+        user_id: i64,
+        sha256: &str,
+        size: i64,
+        content_type: Option<&str>,
+        posted_date: NaiveDateTime,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO attachment_hashes(interview_id, user_id, sha256, size, content_type,
+                    posted_date)
+                VALUES(?, ?, ?, ?, ?, ?)",
+            interview_id,
+            user_id,
+            sha256,
+            size,
+            content_type,
+            posted_date
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// An ID verification interview whose attachments still have to be purged.
 #[derive(FromRow, Debug)]
-pub struct Interview {
-    pub id: i32,
+pub struct AttachmentPurge {
+    pub interview_id: i64,
+
+    #[sqlx(default)]
+    pub request_date: NaiveDateTime,
+}
+
+impl AttachmentPurge {
+    /// Records that the attachments of an interview have to be purged, if not recorded yet.
+    pub async fn insert(executor: impl SqliteExecutor<'_>, interview_id: i64) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO attachment_purges(interview_id) VALUES(?) ON CONFLICT DO NOTHING",
+            interview_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fetches every purge that hasn't succeeded yet, oldest first.
+    pub async fn fetch_all(executor: impl SqliteExecutor<'_>) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as!(
+            Self,
+            "SELECT interview_id, request_date FROM attachment_purges ORDER BY request_date"
+        )
+        .fetch_all(executor)
+        .await
+    }
 
+    /// Removes the purge of an interview once it succeeded.
+    pub async fn delete(executor: impl SqliteExecutor<'_>, interview_id: i64) -> sqlx::Result<()> {
+        sqlx::query!(
+            "DELETE FROM attachment_purges WHERE interview_id = ?",
+            interview_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// The perceptual hash of an image posted during an ID verification interview.
+#[derive(FromRow, Debug)]
+pub struct ImageHash {
+    pub id: i64,
+
+    pub interview_id: i64,
     pub user_id: i64,
-    pub interviewer_id: i64,
 
-    pub r#type: InterviewType,
+    /// 64-bit difference hash of the image, stored as its bits.
+    pub perceptual_hash: i64,
+
+    pub posted_date: NaiveDateTime,
+}
+
+impl ImageHash {
+    /// Records the hash of an image.
+    pub async fn insert(
+        executor: impl SqliteExecutor<'_>,
+        interview_id: i64,
+        user_id: i64,
+        perceptual_hash: i64,
+        posted_date: NaiveDateTime,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO image_hashes(interview_id, user_id, perceptual_hash, posted_date)
+                VALUES(?, ?, ?, ?)",
+            interview_id,
+            user_id,
+            perceptual_hash,
+            posted_date
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fetches the hashes of images posted by every user other than the given one.
+    pub async fn fetch_of_others(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+    ) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as!(
+            Self,
+            "SELECT id, interview_id, user_id, perceptual_hash, posted_date
+                FROM image_hashes WHERE user_id != ? ORDER BY id",
+            user_id
+        )
+        .fetch_all(executor)
+        .await
+    }
+}
+
+/// An application form submitted by a user before their interview.
+#[derive(FromRow, Debug)]
+pub struct Application {
+    pub id: i64,
+
+    pub user_id: i64,
+
+    pub age: i64,
+    /// How the user found the server.
+    pub source: String,
+    /// Why the user wants access.
+    pub reason: String,
+
+    #[sqlx(default)]
+    pub submission_date: NaiveDateTime,
+}
+
+impl Application {
+    /// Records an application of a user.
+    pub async fn insert(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+        age: i64,
+        source: &str,
+        reason: &str,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO applications(user_id, age, source, reason) VALUES(?, ?, ?, ?)",
+            user_id,
+            age,
+            source,
+            reason
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fetches the most recent application of a user.
+    pub async fn latest(
+        executor: impl SqliteExecutor<'_>,
+        user_id: i64,
+    ) -> sqlx::Result<Option<Self>> {
+        sqlx::query_as!(
+            Self,
+            "SELECT id, user_id, age, source, reason, submission_date FROM applications
+                WHERE user_id = ? ORDER BY submission_date DESC, id DESC LIMIT 1",
+            user_id
+        )
+        .fetch_optional(executor)
+        .await
+    }
+}
+
+/// The last seen message of a watched channel, so messages posted while the bot was
+/// offline can be caught up on.
+#[derive(FromRow, Debug)]
+pub struct ChannelCheckpoint {
+    pub channel_id: i64,
+
+    pub message_id: i64,
+
+    #[sqlx(default)]
+    pub update_date: NaiveDateTime,
+}
+
+impl ChannelCheckpoint {
+    /// Fetches the checkpoint of a channel.
+    pub async fn fetch(
+        executor: impl SqliteExecutor<'_>,
+        channel_id: i64,
+    ) -> sqlx::Result<Option<Self>> {
+        sqlx::query_as!(
+            Self,
+            "SELECT channel_id, message_id, update_date FROM channel_checkpoints
+                WHERE channel_id = ?",
+            channel_id
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Fetches the checkpoint of every channel.
+    pub async fn fetch_all(executor: impl SqliteExecutor<'_>) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as!(
+            Self,
+            "SELECT channel_id, message_id, update_date FROM channel_checkpoints"
+        )
+        .fetch_all(executor)
+        .await
+    }
+
+    /// Moves the checkpoint of a channel forward to the given message, if it's newer.
+    pub async fn advance(
+        executor: impl SqliteExecutor<'_>,
+        channel_id: i64,
+        message_id: i64,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO channel_checkpoints(channel_id, message_id) VALUES(?, ?)
+                ON CONFLICT(channel_id) DO UPDATE
+                SET message_id = MAX(message_id, excluded.message_id),
+                    update_date = DATETIME('now')",
+            channel_id,
+            message_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(rename_all = "snake_case")]
+pub enum AuditAction {
+    Marked,
+    /// The user submitted an application form.
+    Applied,
+    Claimed,
+    InterviewStarted,
+    Approved,
+    Rejected,
+    Reverted,
+    Reopened,
+    /// A role was added or removed to match the records.
+    RoleChanged,
+    /// A rejoining member got the outcome of their previous interviews back.
+    Restored,
+    /// A moderator bypassed the interviews, like by granting a verified role by hand.
+    Override,
+}
+
+/// A verification action taken on a user, by a moderator or by the bot itself.
+#[derive(FromRow, Debug)]
+pub struct AuditEntry {
+    pub id: i64,
+
+    pub action: AuditAction,
+
+    /// Who took the action, none if it was the bot.
+    #[sqlx(default)]
+    pub actor_id: Option<i64>,
+    pub target_id: i64,
 
     #[sqlx(default)]
-    pub interview_date: NaiveDateTime,
+    pub status_before: Option<UserStatus>,
+    #[sqlx(default)]
+    pub status_after: Option<UserStatus>,
+
+    #[sqlx(default)]
+    pub reason: Option<String>,
+
+    #[sqlx(default)]
+    pub date: NaiveDateTime,
+}
+
+impl AuditEntry {
+    /// Records an action taken on a user, along with the status change it caused.
+    pub async fn record(
+        executor: impl SqliteExecutor<'_>,
+        action: AuditAction,
+        actor_id: Option<i64>,
+        target_id: i64,
+        transition: Option<Transition>,
+        reason: Option<&str>,
+    ) -> sqlx::Result<()> {
+        let status_before = transition.and_then(Transition::from);
+        let status_after = transition.map(Transition::to);
+        sqlx::query!(
+            "INSERT INTO audit_log(action, actor_id, target_id, status_before, status_after,
+                    reason)
+                VALUES(?, ?, ?, ?, ?, ?)",
+            action,
+            actor_id,
+            target_id,
+            status_before,
+            status_after,
+            reason
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fetches the latest actions taken on a user, newest first.
+    pub async fn fetch_latest(
+        executor: impl SqliteExecutor<'_>,
+        target_id: i64,
+        limit: i64,
+    ) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as!(
+            Self,
+            r#"SELECT id, action AS "action: AuditAction", actor_id, target_id,
+                    status_before AS "status_before: UserStatus",
+                    status_after AS "status_after: UserStatus", reason, date
+                FROM audit_log WHERE target_id = ?
+                ORDER BY date DESC, id DESC LIMIT ?"#,
+            target_id,
+            limit
+        )
+        .fetch_all(executor)
+        .await
+    }
+
+    /// Fetches every recorded action, oldest first.
+    pub async fn fetch_all(executor: impl SqliteExecutor<'_>) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as!(
+            Self,
+            r#"SELECT id, action AS "action: AuditAction", actor_id, target_id,
+                    status_before AS "status_before: UserStatus",
+                    status_after AS "status_after: UserStatus", reason, date
+                FROM audit_log ORDER BY date ASC, id ASC"#
+        )
+        .fetch_all(executor)
+        .await
+    }
 }
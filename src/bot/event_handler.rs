@@ -1,14 +1,27 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 
-use serenity::all::{Context, EventHandler, Interaction, Member, Message, MessageType, Ready};
+use chrono::Utc;
+use serenity::all::{
+    Context, CreateMessage, EventHandler, GuildChannel, GuildMemberUpdateEvent, Interaction,
+    Member, Mentionable, Message, MessageType, Ready,
+};
 use tokio::sync::RwLock;
 use tokio::time::{self, Duration};
 use tracing::{debug, error, info, trace, warn};
 
 use super::commands::run_command;
-use super::helpers::interaction_context::CommandInteractionContext;
+use super::components::{run_component, run_modal};
+use super::helpers::interaction_context::{
+    CommandInteractionContext, ComponentInteractionContext, ModalInteractionContext,
+};
 
-use crate::bot::{commands::register_commands, context::BouncerContext, database};
+use crate::bot::{
+    backfill, commands::register_commands, context::BouncerContext, database, id_verification,
+    manual_grants, marking, marks, questionnaire, rejoin,
+};
 use crate::config;
 
 macro_rules! error_exit {
@@ -18,9 +31,234 @@ macro_rules! error_exit {
     };
 }
 
+#[derive(Clone)]
 pub struct BouncerEventHandler {
-    pub discord_config: config::Discord,
+    pub discord_config: Arc<config::Discord>,
     pub state: Arc<RwLock<super::BouncerState>>,
+
+    /// The newest message the backfill is responsible for, older ones are left to it.
+    pub backfill_until: Arc<AtomicU64>,
+}
+
+impl BouncerEventHandler {
+    async fn mark_joined_member(context: &Context, state: &super::BouncerState, member: &Member) {
+        let user_id = database::to_id(member.user.id.get());
+        match marking::record_join(&state.database, user_id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                debug!("joined member `{user_id}` is in the database records already, skipping...");
+                return;
+            }
+            Err(error) => {
+                error!("failed to insert a new user `{user_id}` into the `users` table: {error}");
+                return;
+            }
+        }
+        debug!("added a new user `{user_id}` to the database with status `pending` on join");
+
+        marks::sync_or_warn(&context.http, state, member.user.id).await;
+    }
+
+    async fn give_pending_role(context: &Context, state: &super::BouncerState, member: &Member) {
+        match member
+            .add_role(
+                &context.http,
+                state.context.roles.pending_interview.id,
+                Some("New member awaiting interview."),
+            )
+            .await
+        {
+            Ok(()) => debug!(
+                "gave the pending interview role to the new member `{}`",
+                member.user.id
+            ),
+            Err(error) => error!(
+                "could not give the pending interview role to `{}`: {error}",
+                member.user.id
+            ),
+        }
+    }
+
+    pub fn is_watched(&self, channel: &GuildChannel) -> bool {
+        self.discord_config.rules.is_watched(
+            channel.id.get(),
+            channel.parent_id.map(|parent_id| parent_id.get()),
+            channel.nsfw,
+        )
+    }
+
+    /// Runs the marking logic over a message from a watched channel.
+    pub async fn process_message(
+        &self,
+        context: &Context,
+        state: &super::BouncerState,
+        message: &Message,
+        message_channel: &GuildChannel,
+    ) {
+        let Ok(message_member) = message.member(&context.http).await else {
+            error!("could not fetch the message member, skipping...");
+            return;
+        };
+        if state.context.guild.owner_id == message_member.user.id {
+            debug!("message is from the guild owner, ignoring...");
+            return;
+        }
+        if state.context.roles.is_interviewer(&message_member.roles) {
+            debug!("message is from an interviewer, ignoring...");
+            return;
+        }
+        if self
+            .discord_config
+            .rules
+            .is_exempt_role(message_member.roles.iter().map(|role_id| role_id.get()))
+        {
+            debug!("message is from a member with an exempt role, ignoring...");
+            return;
+        }
+        let user_id = message_member.user.id;
+        let outcome = match marking::record_message(
+            &state.database,
+            database::to_id(user_id.get()),
+            database::to_id(message.channel_id.get()),
+            database::to_id(message.id.get()),
+            //* Users with records are handled either way, so pending users are still
+            //* restricted when they are marked on join.
+            self.discord_config.rules.mark_on == config::MarkOn::FirstMessage,
+            self.discord_config.rules.report_interval_minutes,
+        )
+        .await
+        {
+            Ok(outcome) => outcome,
+            Err(error) => {
+                error!("could not record the message of `{user_id}`, skipping... ({error})");
+                return;
+            }
+        };
+
+        match outcome {
+            marking::Outcome::Marked => {
+                debug!("added a new user `{user_id}` to the database with status `pending`");
+                marks::sync_or_warn(&context.http, state, user_id).await;
+            }
+            marking::Outcome::Refreshed => {
+                debug!("refreshed the mark of the pending user `{user_id}`");
+                marks::sync_or_warn(&context.http, state, user_id).await;
+            }
+            marking::Outcome::RecentlyRefreshed => {
+                debug!(
+                    "the mark of the pending user `{user_id}` was refreshed recently, ignoring..."
+                );
+            }
+            marking::Outcome::Flagged => {
+                debug!("flagging the rejected user `{user_id}` again");
+                if let Err(error) = marks::flag(
+                    &context.http,
+                    state,
+                    user_id,
+                    message.channel_id,
+                    message.id,
+                )
+                .await
+                {
+                    error!("could not flag the rejected user `{user_id}`: {error}");
+                }
+            }
+            marking::Outcome::RecentlyFlagged => {
+                debug!("the rejected user `{user_id}` was flagged recently, ignoring...");
+            }
+            marking::Outcome::Ignored => {
+                debug!("user `{user_id}` is being interviewed or approved, ignoring...");
+            }
+            marking::Outcome::Unmarked => {
+                debug!(
+                    "user `{user_id}` has no records and is not marked on messages, ignoring..."
+                );
+            }
+        }
+
+        if matches!(
+            outcome,
+            marking::Outcome::Marked
+                | marking::Outcome::Refreshed
+                | marking::Outcome::RecentlyRefreshed
+        ) && self.discord_config.rules.is_enforced(
+            message_channel.id.get(),
+            message_channel.parent_id.map(|parent_id| parent_id.get()),
+        ) {
+            Self::enforce_restriction(
+                context,
+                state,
+                message,
+                &message_member,
+                matches!(
+                    outcome,
+                    marking::Outcome::Marked | marking::Outcome::Refreshed
+                ),
+            )
+            .await;
+        }
+    }
+
+    /// Deletes a message of an unverified user from an enforced channel, gives them the
+    /// pending interview role and lets them know why, unless they were told recently.
+    async fn enforce_restriction(
+        context: &Context,
+        state: &super::BouncerState,
+        message: &Message,
+        member: &Member,
+        notify: bool,
+    ) {
+        let reason = Some("Unverified user posted in a restricted channel.");
+
+        if let Err(error) = message
+            .channel_id
+            .delete_message(&context.http, message.id, reason)
+            .await
+        {
+            error!(
+                "could not delete the message of the unverified user `{}`: {error}",
+                member.user.id
+            );
+        }
+
+        if let Err(error) = member
+            .add_role(
+                &context.http,
+                state.context.roles.pending_interview.id,
+                reason,
+            )
+            .await
+        {
+            error!(
+                "could not give the pending interview role to `{}`: {error}",
+                member.user.id
+            );
+        }
+
+        if !notify {
+            return;
+        }
+        if let Err(error) = member
+            .user
+            .id
+            .direct_message(
+                &context.http,
+                CreateMessage::new().content(format!(
+                    "Your message in {} was removed, since you need to be interviewed before \
+                     posting there. An interviewer from **{}** will reach out to you soon, so \
+                     please keep your direct messages open.",
+                    message.channel_id.mention(),
+                    state.context.guild.name
+                )),
+            )
+            .await
+        {
+            debug!(
+                "could not send a direct message to `{}`: {error}",
+                member.user.id
+            );
+        }
+    }
 }
 
 #[serenity::async_trait]
@@ -28,6 +266,15 @@ impl EventHandler for BouncerEventHandler {
     async fn cache_ready(&self, context: Context, _guilds: Vec<serenity::model::id::GuildId>) {
         trace!("running the `cache_ready` event handler...");
 
+        //* The checkpoints are captured before messages are handled live, since live
+        //* handling moves them past the messages posted while offline.
+        let checkpoints =
+            database::ChannelCheckpoint::fetch_all(&self.state.read().await.database).await;
+        let until = backfill::message_id_at(Utc::now());
+        if checkpoints.is_ok() {
+            self.backfill_until.store(until.get(), Ordering::Release);
+        }
+
         match BouncerContext::try_populate(&context, &self.discord_config) {
             Ok(context) => self.state.write().await.context = context,
             Err(error) => {
@@ -35,39 +282,50 @@ impl EventHandler for BouncerEventHandler {
             }
         }
 
+        match checkpoints {
+            Ok(checkpoints) => {
+                tokio::spawn(backfill::run(self.clone(), context, checkpoints, until));
+            }
+            Err(error) => {
+                error!(
+                    "could not fetch the channel checkpoints, skipping the backfill... ({error})"
+                );
+            }
+        }
+
         trace!("ran the `cache_ready` event handler");
     }
 
     async fn guild_member_addition(&self, context: Context, member: Member) {
         let state = self.state.read().await;
 
-        let user_id =
-            i64::try_from(member.user.id.get()).expect("failed to convert user ID from u64 to i64");
-        match sqlx::query!(
-            "SELECT user_id, type FROM interviews WHERE user_id = ?",
-            user_id
-        )
-        .fetch_optional(&state.database)
-        .await
-        {
+        let user_id = database::to_id(member.user.id.get());
+        match database::User::fetch(&state.database, user_id).await {
             Ok(Some(user)) => {
-                if let Err(error) = member
-                    .add_role(
-                        &context.http,
-                        if user.r#type == database::InterviewType::Text.to_string() {
-                            state.context.roles.text_verified.id
-                        } else {
-                            state.context.roles.id_verified.id
-                        },
-                        Some("Was previously interviewed."),
-                    )
+                match rejoin::restore(&context.http, &state, &self.discord_config, &member, &user)
                     .await
                 {
-                    error!("an unexpected error occurred while adding roles back to a member: {error:#?}");
+                    Ok(restoration) => {
+                        info!("restored rejoined member `{user_id}`: {restoration:?}");
+                    }
+                    Err(error) => {
+                        error!("an unexpected error occurred while restoring a rejoined member: {error:#?}");
+                    }
                 }
             }
             Ok(None) => {
                 info!("joined member `{user_id}` is new, no roles to give back");
+
+                let rules = &self.discord_config.rules;
+                if member.user.bot() {
+                    return;
+                }
+                if rules.pending_role_on_join {
+                    Self::give_pending_role(&context, &state, &member).await;
+                }
+                if rules.mark_on == config::MarkOn::Join || rules.pending_role_on_join {
+                    Self::mark_joined_member(&context, &state, &member).await;
+                }
             }
             Err(error) => {
                 error!("database query error: {error}");
@@ -75,106 +333,116 @@ impl EventHandler for BouncerEventHandler {
         }
     }
 
-    async fn message(&self, context: Context, message: Message) {
+    async fn guild_member_update(
+        &self,
+        context: Context,
+        old_if_available: Option<Member>,
+        _new: Option<Member>,
+        event: GuildMemberUpdateEvent,
+    ) {
         let state = self.state.read().await;
         if !state.context.is_populated() {
-            warn!("context is not populated yet, ignoring messages until it's populated...");
+            warn!("context is not populated yet, ignoring member updates until it's populated...");
             return;
         }
-
-        if message.kind != MessageType::Regular {
-            trace!("message is a system message, ignoring...");
+        if event.guild_id != state.context.guild.id || event.user.bot() {
             return;
         }
 
-        let message_guild = match message.guild_id {
-            Some(guild_id) => match guild_id.to_partial_guild(&context.http).await {
-                Ok(guild) => guild,
-                Err(error) => {
-                    error!(
-                        "could not fetch the guild data of `{}`, skipping... ({error})",
-                        guild_id
-                    );
-                    return;
-                }
-            },
-            None => {
-                debug!("message is not from a guild, ignoring...");
-                return;
+        //* Members are not chunked, so their previous roles are mostly unknown. Every tier role
+        //* they hold is checked then, the audit log tells whether it was granted by hand.
+        let old_role_ids = old_if_available.map(|old| old.roles);
+        for tier in &state.context.tiers {
+            let role_id = tier.role.id;
+            if !event.roles.contains(&role_id)
+                || old_role_ids
+                    .as_ref()
+                    .is_some_and(|old_role_ids| old_role_ids.contains(&role_id))
+            {
+                continue;
             }
-        };
-        let Ok(message_channel) = message.guild_channel(&context.http).await else {
-            error!("could not find the channel, skipping...");
-            return;
-        };
-        let Ok(message_member) = message.member(&context.http).await else {
-            error!("could not fetch the message member, skipping...");
+
+            if let Err(error) = manual_grants::handle(
+                &context.http,
+                &state,
+                &self.discord_config,
+                event.user.id,
+                tier,
+            )
+            .await
+            {
+                error!(
+                    "an error occurred while handling the verified role granted to `{}`: {error:#?}",
+                    event.user.id
+                );
+            }
+        }
+    }
+
+    async fn message(&self, context: Context, message: Message) {
+        let state = self.state.read().await;
+        if !state.context.is_populated() {
+            warn!("context is not populated yet, ignoring messages until it's populated...");
             return;
-        };
+        }
 
-        // TODO: Sort these based on the priorities.
-        if message_guild.owner_id == message_member.user.id {
-            debug!("message is from the guild owner, ignoring...");
+        if message.kind != MessageType::Regular {
+            trace!("message is a system message, ignoring...");
             return;
         }
-        if !message_channel.nsfw {
-            debug!("message is not from an NSFW channel, ignoring...");
+
+        if message.guild_id != Some(state.context.guild.id) {
+            debug!("message is not from the guild, ignoring...");
             return;
         }
-        if message_member.user.bot() {
+        if message.author.bot() {
             debug!("message is from a bot, ignoring...");
             return;
         }
-        if message_member.roles.iter().any(|role_id| {
-            state
-                .context
-                .roles
-                .interviewers
-                .iter()
-                .any(|role| role.id == *role_id)
-        }) {
-            debug!("message is from an interviewer, ignoring...");
-            return;
+
+        id_verification::check_images_or_warn(&context.http, &state, &message).await;
+        match questionnaire::record_answer(&context.http, &state, &message).await {
+            Ok(true) => {
+                debug!("recorded an interview answer of `{}`", message.author.id);
+                return;
+            }
+            Ok(false) => {}
+            Err(error) => {
+                error!(
+                    "could not record the interview answer of `{}`, skipping... ({error})",
+                    message.author.id
+                );
+                return;
+            }
         }
-        let Ok(user_status) = sqlx::query!("SELECT status FROM users")
-            .fetch_optional(&state.database)
-            .await
-        else {
-            error!(
-                "could not read the user data of `{}`, skipping...",
-                message_member.user.id
-            );
+        let Ok(message_channel) = message.guild_channel(&context.http).await else {
+            error!("could not find the channel, skipping...");
             return;
         };
-        if user_status.is_some() {
-            debug!("this user is in the database records already, skipping...");
+        if !self.is_watched(&message_channel) {
+            debug!("message is not from a watched channel, ignoring...");
             return;
         }
-        let user_id = i64::try_from(message_member.user.id.get())
-            .expect("failed to convert user ID from u64 to i64");
-        match sqlx::query_as!(
-            User,
-            "INSERT INTO users(user_id, status) VALUES(?, ?)",
-            user_id,
-            database::UserStatus::Pending
+        if message.id.get() <= self.backfill_until.load(Ordering::Acquire) {
+            debug!("message was posted before the backfill started, leaving it to the backfill...");
+            return;
+        }
+
+        if let Err(error) = database::ChannelCheckpoint::advance(
+            &state.database,
+            database::to_id(message_channel.id.get()),
+            database::to_id(message.id.get()),
         )
-        .execute(&state.database)
         .await
         {
-            Ok(_) => {
-                debug!(
-                    "added a new user `{}` to the database with status `pending`",
-                    message_member.user.id
-                );
-            }
-            Err(error) => {
-                error!(
-                    "failed to insert a new user `{}` into the `users` table: {error}",
-                    message_member.user.id
-                );
-                return;
-            }
+            warn!(
+                "could not advance the checkpoint of `{}`: {error}",
+                message_channel.id
+            );
         }
+
+        self.process_message(&context, &state, &message, &message_channel)
+            .await;
     }
 
     async fn ready(&self, context: Context, ready: Ready) {
@@ -195,23 +463,52 @@ impl EventHandler for BouncerEventHandler {
             counter += 1;
         }
 
-        register_commands(&self.state.read().await.context.guild, &context).await;
+        register_commands(&self.state.read().await.context, &context).await;
     }
 
     async fn interaction_create(&self, context: Context, interaction: Interaction) {
-        if let Interaction::Command(command_interaction) = interaction {
-            let interaction_context = CommandInteractionContext {
-                context: &context,
-                interaction: &command_interaction,
-                options: &command_interaction.data.options(),
-            };
-
-            if let Err(error) = run_command(interaction_context, self.state.clone()).await {
-                error!(
-                    "an error occurred while running `{interaction_name}` command interaction: {error:#?}",
-                    interaction_name = command_interaction.data.name
-                );
+        match interaction {
+            Interaction::Command(command_interaction) => {
+                let interaction_context = CommandInteractionContext {
+                    context: &context,
+                    interaction: &command_interaction,
+                    options: &command_interaction.data.options(),
+                };
+
+                if let Err(error) = run_command(interaction_context, self.state.clone()).await {
+                    error!(
+                        "an error occurred while running `{interaction_name}` command interaction: {error:#?}",
+                        interaction_name = command_interaction.data.name
+                    );
+                }
+            }
+            Interaction::Component(component_interaction) => {
+                let interaction_context = ComponentInteractionContext {
+                    context: &context,
+                    interaction: &component_interaction,
+                };
+
+                if let Err(error) = run_component(interaction_context, self.state.clone()).await {
+                    error!(
+                        "an error occurred while running `{custom_id}` component interaction: {error:#?}",
+                        custom_id = component_interaction.data.custom_id
+                    );
+                }
+            }
+            Interaction::Modal(modal_interaction) => {
+                let interaction_context = ModalInteractionContext {
+                    context: &context,
+                    interaction: &modal_interaction,
+                };
+
+                if let Err(error) = run_modal(interaction_context, self.state.clone()).await {
+                    error!(
+                        "an error occurred while running `{custom_id}` modal interaction: {error:#?}",
+                        custom_id = modal_interaction.data.custom_id
+                    );
+                }
             }
+            _ => {}
         }
     }
 }
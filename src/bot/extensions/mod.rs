@@ -1 +1,2 @@
+pub mod modal_components;
 pub mod resolved_options;
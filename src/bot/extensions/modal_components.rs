@@ -0,0 +1,18 @@
+use serenity::all::{ActionRow, ActionRowComponent};
+
+pub trait ModalComponentsExt {
+    fn get_input_text(&self, custom_id: &str) -> Option<&str>;
+}
+
+impl ModalComponentsExt for &[ActionRow] {
+    fn get_input_text(&self, custom_id: &str) -> Option<&str> {
+        self.iter()
+            .flat_map(|row| row.components.iter())
+            .find_map(|component| match component {
+                ActionRowComponent::InputText(input) if input.custom_id == custom_id => {
+                    input.value.as_deref()
+                }
+                _ => None,
+            })
+    }
+}
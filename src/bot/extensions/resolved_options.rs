@@ -3,6 +3,10 @@ use serenity::all::{PartialMember, ResolvedOption, ResolvedValue, User};
 pub trait ResolvedOptionExt {
     fn get_string_option(&self, name: &str) -> Option<&str>;
 
+    fn get_bool_option(&self, name: &str) -> Option<bool>;
+
+    fn get_integer_option(&self, name: &str) -> Option<i64>;
+
     fn get_user_and_member(&self, index: usize) -> Option<(&User, Option<&PartialMember>)>;
 }
 
@@ -19,6 +23,30 @@ impl ResolvedOptionExt for &[ResolvedOption<'_>] {
             })
     }
 
+    fn get_bool_option(&self, name: &str) -> Option<bool> {
+        self.iter()
+            .find(|option| option.name == name)
+            .and_then(|option| {
+                if let ResolvedValue::Boolean(value) = option.value {
+                    Some(value)
+                } else {
+                    None
+                }
+            })
+    }
+
+    fn get_integer_option(&self, name: &str) -> Option<i64> {
+        self.iter()
+            .find(|option| option.name == name)
+            .and_then(|option| {
+                if let ResolvedValue::Integer(value) = option.value {
+                    Some(value)
+                } else {
+                    None
+                }
+            })
+    }
+
     fn get_user_and_member(&self, index: usize) -> Option<(&User, Option<&PartialMember>)> {
         match self.get(index) {
             Some(ResolvedOption {
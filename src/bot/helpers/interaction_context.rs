@@ -1,5 +1,6 @@
 use serenity::all::{
-    CommandInteraction, Context, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CommandInteraction, ComponentInteraction, Context, CreateInteractionResponse,
+    CreateInteractionResponseMessage, CreateModal, EditInteractionResponse, ModalInteraction,
     ResolvedOption,
 };
 
@@ -10,6 +11,70 @@ pub struct CommandInteractionContext<'a> {
 }
 
 impl<'a> CommandInteractionContext<'a> {
+    pub async fn reply(
+        &self,
+        message: CreateInteractionResponseMessage<'_>,
+    ) -> anyhow::Result<(), serenity::Error> {
+        self.interaction
+            .create_response(
+                &self.context.http,
+                CreateInteractionResponse::Message(message),
+            )
+            .await
+    }
+
+    pub async fn reply_string(
+        &self,
+        message: impl ToString + Send,
+        ephemeral: Option<bool>,
+    ) -> anyhow::Result<(), serenity::Error> {
+        self.interaction
+            .create_response(
+                &self.context.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(message.to_string())
+                        .ephemeral(ephemeral.unwrap_or(false)),
+                ),
+            )
+            .await
+    }
+
+    /// Acknowledges the interaction for replies that take longer than Discord waits for.
+    pub async fn defer(&self, ephemeral: Option<bool>) -> anyhow::Result<(), serenity::Error> {
+        if ephemeral.unwrap_or(false) {
+            self.interaction.defer_ephemeral(&self.context.http).await
+        } else {
+            self.interaction.defer(&self.context.http).await
+        }
+    }
+
+    pub async fn edit_reply_string(
+        &self,
+        message: impl ToString + Send,
+    ) -> anyhow::Result<(), serenity::Error> {
+        self.interaction
+            .edit_response(
+                &self.context.http,
+                EditInteractionResponse::new().content(message.to_string()),
+            )
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn reply_modal(&self, modal: CreateModal<'_>) -> anyhow::Result<(), serenity::Error> {
+        self.interaction
+            .create_response(&self.context.http, CreateInteractionResponse::Modal(modal))
+            .await
+    }
+}
+
+pub struct ComponentInteractionContext<'a> {
+    pub context: &'a Context,
+    pub interaction: &'a ComponentInteraction,
+}
+
+impl<'a> ComponentInteractionContext<'a> {
     pub async fn reply_string(
         &self,
         message: impl ToString + Send,
@@ -26,4 +91,105 @@ impl<'a> CommandInteractionContext<'a> {
             )
             .await
     }
+
+    /// Acknowledges the interaction with a reply to edit later, for replies that take longer
+    /// than Discord waits for.
+    pub async fn defer_reply(
+        &self,
+        ephemeral: Option<bool>,
+    ) -> anyhow::Result<(), serenity::Error> {
+        //* A plain deferral of a component acknowledges an update of its message instead.
+        self.interaction
+            .create_response(
+                &self.context.http,
+                CreateInteractionResponse::Defer(
+                    CreateInteractionResponseMessage::new().ephemeral(ephemeral.unwrap_or(false)),
+                ),
+            )
+            .await
+    }
+
+    pub async fn edit_reply_string(
+        &self,
+        message: impl ToString + Send,
+    ) -> anyhow::Result<(), serenity::Error> {
+        self.interaction
+            .edit_response(
+                &self.context.http,
+                EditInteractionResponse::new().content(message.to_string()),
+            )
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn update_message(
+        &self,
+        message: CreateInteractionResponseMessage<'_>,
+    ) -> anyhow::Result<(), serenity::Error> {
+        self.interaction
+            .create_response(
+                &self.context.http,
+                CreateInteractionResponse::UpdateMessage(message),
+            )
+            .await
+    }
+
+    pub async fn reply_modal(&self, modal: CreateModal<'_>) -> anyhow::Result<(), serenity::Error> {
+        self.interaction
+            .create_response(&self.context.http, CreateInteractionResponse::Modal(modal))
+            .await
+    }
+}
+
+pub struct ModalInteractionContext<'a> {
+    pub context: &'a Context,
+    pub interaction: &'a ModalInteraction,
+}
+
+impl<'a> ModalInteractionContext<'a> {
+    pub async fn reply_string(
+        &self,
+        message: impl ToString + Send,
+        ephemeral: Option<bool>,
+    ) -> anyhow::Result<(), serenity::Error> {
+        self.interaction
+            .create_response(
+                &self.context.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(message.to_string())
+                        .ephemeral(ephemeral.unwrap_or(false)),
+                ),
+            )
+            .await
+    }
+
+    /// Acknowledges the interaction with a reply to edit later, for replies that take longer
+    /// than Discord waits for.
+    pub async fn defer_reply(
+        &self,
+        ephemeral: Option<bool>,
+    ) -> anyhow::Result<(), serenity::Error> {
+        self.interaction
+            .create_response(
+                &self.context.http,
+                CreateInteractionResponse::Defer(
+                    CreateInteractionResponseMessage::new().ephemeral(ephemeral.unwrap_or(false)),
+                ),
+            )
+            .await
+    }
+
+    pub async fn edit_reply_string(
+        &self,
+        message: impl ToString + Send,
+    ) -> anyhow::Result<(), serenity::Error> {
+        self.interaction
+            .edit_response(
+                &self.context.http,
+                EditInteractionResponse::new().content(message.to_string()),
+            )
+            .await
+            .map(|_| ())
+    }
 }
@@ -0,0 +1,349 @@
+//! Attachments of ID verification interviews, which are deleted when the interviews end so
+//! ID images don't stay on Discord, with only their hashes kept. Images are also compared
+//! against the ones of other users as they're posted, to catch reused ID photos.
+
+use image::imageops::FilterType;
+use serenity::{
+    all::{ChannelId, Http, Message, UserId},
+    http::{HttpError, StatusCode},
+};
+use sha2::{Digest, Sha256};
+use tracing::{debug, info, warn};
+
+use super::{
+    database::{self, AttachmentHash, AttachmentPurge, ImageHash, Interview},
+    marks, transcript, BouncerState,
+};
+
+/// How many of the 64 bits of two perceptual hashes can differ for the images to be
+/// considered the same.
+//* Recompressing, resizing or cropping a little flips a few bits, while unrelated images
+//* differ by around half of them.
+const MAX_DISTANCE: u32 = 10;
+
+/// Attachments larger than this many bytes are deleted without being downloaded and hashed.
+pub const MAX_DOWNLOAD_SIZE: u32 = 25 * 1024 * 1024;
+
+/// Computes the difference hash of an image: each bit tells whether a pixel is brighter than
+/// the one to its right, on a 9×8 grayscale thumbnail of the image.
+pub fn perceptual_hash(bytes: &[u8]) -> image::ImageResult<u64> {
+    let thumbnail = image::load_from_memory(bytes)?
+        .resize_exact(9, 8, FilterType::Triangle)
+        .into_luma8();
+
+    let mut hash = 0;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if thumbnail.get_pixel(x, y)[0] > thumbnail.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+
+    Ok(hash)
+}
+
+/// How many bits differ between two perceptual hashes.
+pub const fn distance(first: u64, second: u64) -> u32 {
+    (first ^ second).count_ones()
+}
+
+/// Hashes the images an interviewee posts in the channel of their ongoing ID verification
+/// interview and flags the interviewer of every image that closely matches one posted by
+/// another user. Returns how many images matched.
+pub async fn check_images(
+    http: &Http,
+    state: &BouncerState,
+    message: &Message,
+) -> anyhow::Result<usize> {
+    if message.attachments.is_empty() {
+        return Ok(0);
+    }
+    let Some(interview) = Interview::fetch_ongoing_by_channel(
+        &state.database,
+        database::to_id(message.channel_id.get()),
+    )
+    .await?
+    else {
+        return Ok(0);
+    };
+    if interview.user_id != database::to_id(message.author.id.get())
+        || !state
+            .context
+            .tier(&interview.tier)
+            .is_some_and(|tier| tier.id_verification)
+    {
+        return Ok(0);
+    }
+
+    let others = ImageHash::fetch_of_others(&state.database, interview.user_id).await?;
+    let mut matched = 0;
+    for attachment in &message.attachments {
+        if !attachment
+            .content_type
+            .as_deref()
+            .is_some_and(|content_type| content_type.starts_with("image/"))
+        {
+            continue;
+        }
+
+        if attachment.size > MAX_DOWNLOAD_SIZE {
+            debug!(
+                "attachment `{}` of the interview `{}` is too large to check, skipping...",
+                attachment.id, interview.id
+            );
+            continue;
+        }
+        let bytes = match attachment.download().await {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                warn!(
+                    "could not download the attachment `{}` of the interview `{}`, skipping... ({error})",
+                    attachment.id, interview.id
+                );
+                continue;
+            }
+        };
+        //* Decoding and resizing is CPU bound, so it's kept off the async workers.
+        let hash = match tokio::task::spawn_blocking(move || perceptual_hash(&bytes)).await? {
+            Ok(hash) => hash,
+            Err(error) => {
+                debug!(
+                    "could not decode the attachment `{}` of the interview `{}`, skipping... ({error})",
+                    attachment.id, interview.id
+                );
+                continue;
+            }
+        };
+        //* Only the bits of the hash are stored, the image itself is never kept. The casts
+        //* keep the bits as they are.
+        ImageHash::insert(
+            &state.database,
+            interview.id,
+            interview.user_id,
+            hash as i64,
+            transcript::message_date(message),
+        )
+        .await?;
+
+        let mut matches = others
+            .iter()
+            .map(|other| (other, distance(hash, other.perceptual_hash as u64)))
+            .filter(|(_, distance)| *distance <= MAX_DISTANCE)
+            .collect::<Vec<_>>();
+        if matches.is_empty() {
+            continue;
+        }
+        matches.sort_by_key(|(_, distance)| *distance);
+
+        warn!(
+            "the attachment `{}` of the interview `{}` matches {} images of other users",
+            attachment.id,
+            interview.id,
+            matches.len()
+        );
+        marks::log_reused_image(
+            http,
+            state,
+            &interview,
+            message,
+            &attachment.filename,
+            &matches,
+        )
+        .await?;
+        matched += 1;
+    }
+
+    Ok(matched)
+}
+
+/// Checks the images of a message, logging instead of failing, since the interview goes on
+/// either way.
+pub async fn check_images_or_warn(http: &Http, state: &BouncerState, message: &Message) {
+    if let Err(error) = check_images(http, state, message).await {
+        warn!(
+            "could not check the images of the message `{}`: {error}",
+            message.id
+        );
+    }
+}
+
+/// Deletes every message with attachments the interviewee posted in the channel of an
+/// interview, recording the hashes of the attachments. Returns how many were deleted.
+pub async fn purge_attachments(
+    http: &Http,
+    state: &BouncerState,
+    interview: &Interview,
+) -> anyhow::Result<usize> {
+    let Some(channel_id) = interview.channel_id else {
+        return Ok(0);
+    };
+    let channel_id = ChannelId::new(database::from_id(channel_id));
+    let user_id = UserId::new(database::from_id(interview.user_id));
+
+    let messages = match transcript::fetch_messages(http, channel_id).await {
+        Ok(messages) => messages,
+        //* The attachments went away along with a deleted channel.
+        Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(response)))
+            if response.status_code == StatusCode::NOT_FOUND =>
+        {
+            debug!(
+                "channel of the interview `{}` is gone, nothing to purge",
+                interview.id
+            );
+            return Ok(0);
+        }
+        Err(error) => return Err(error.into()),
+    };
+
+    let mut purged = 0;
+    for message in messages {
+        if message.author.id != user_id || message.attachments.is_empty() {
+            continue;
+        }
+
+        let mut hashes = Vec::new();
+        for attachment in &message.attachments {
+            if attachment.size > MAX_DOWNLOAD_SIZE {
+                debug!(
+                    "attachment `{}` of the interview `{}` is too large to hash, deleting it without a hash...",
+                    attachment.id, interview.id
+                );
+                continue;
+            }
+
+            match attachment.download().await {
+                Ok(bytes) => hashes.push((
+                    format!("{:x}", Sha256::digest(&bytes)),
+                    i64::from(attachment.size),
+                    attachment.content_type.as_deref(),
+                )),
+                Err(error) => warn!(
+                    "could not download the attachment `{}` of the interview `{}`, deleting it without a hash... ({error})",
+                    attachment.id, interview.id
+                ),
+            }
+        }
+
+        //* Attachments can't be removed from messages of others, so the whole message goes.
+        //* Its content is captured in the transcript beforehand.
+        channel_id
+            .delete_message(
+                http,
+                message.id,
+                Some("ID verification attachments are not kept."),
+            )
+            .await?;
+
+        //* Only recorded once the message is gone, so a retried purge doesn't hash its
+        //* attachments twice.
+        let posted_date = transcript::message_date(&message);
+        let mut transaction = state.database.begin().await?;
+        for (sha256, size, content_type) in hashes {
+            AttachmentHash::insert(
+                &mut *transaction,
+                interview.id,
+                interview.user_id,
+                &sha256,
+                size,
+                content_type,
+                posted_date,
+            )
+            .await?;
+        }
+        transaction.commit().await?;
+
+        purged += message.attachments.len();
+    }
+
+    info!(
+        "purged {purged} attachments of the interview `{}`",
+        interview.id
+    );
+
+    Ok(purged)
+}
+
+/// Purges the attachments of an interview, logging instead of failing, since the interview
+/// ends either way. The purge is recorded beforehand and only cleared once it succeeds, so
+/// the `attachment_purges` job retries it otherwise.
+pub async fn purge_attachments_or_warn(http: &Http, state: &BouncerState, interview: &Interview) {
+    if let Err(error) = AttachmentPurge::insert(&state.database, interview.id).await {
+        warn!(
+            "could not record the purge of the interview `{}`, purging without retries... ({error})",
+            interview.id
+        );
+    }
+
+    retry_purge_or_warn(http, state, interview).await;
+}
+
+/// Purges the attachments of an interview whose purge is recorded, clearing the record once
+/// it succeeds.
+pub async fn retry_purge_or_warn(http: &Http, state: &BouncerState, interview: &Interview) {
+    if let Err(error) = purge_attachments(http, state, interview).await {
+        warn!(
+            "could not purge the attachments of the interview `{}`, retrying later... ({error})",
+            interview.id
+        );
+        return;
+    }
+
+    if let Err(error) = AttachmentPurge::delete(&state.database, interview.id).await {
+        warn!(
+            "could not clear the purge of the interview `{}`: {error}",
+            interview.id
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use image::{ImageFormat, RgbImage};
+
+    use super::{distance, perceptual_hash, MAX_DISTANCE};
+
+    /// Encodes a pattern of diagonal stripes, mirrored horizontally if asked to.
+    fn stripes(width: u32, height: u32, mirrored: bool, format: ImageFormat) -> Vec<u8> {
+        let image = RgbImage::from_fn(width, height, |x, y| {
+            let x = if mirrored { width - 1 - x } else { x };
+            let shade = u8::try_from((x * 7 / width + y * 3 / height) % 5 * 50).unwrap();
+            image::Rgb([shade, shade, shade / 2])
+        });
+
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, format).unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn counts_differing_bits() {
+        assert_eq!(distance(0, 0), 0);
+        assert_eq!(distance(0b1011, 0b0010), 2);
+        assert_eq!(distance(u64::MAX, 0), 64);
+    }
+
+    #[test]
+    fn matches_resized_and_recompressed_images() {
+        let original = perceptual_hash(&stripes(400, 300, false, ImageFormat::Png)).unwrap();
+        let copy = perceptual_hash(&stripes(200, 150, false, ImageFormat::Jpeg)).unwrap();
+
+        assert!(distance(original, copy) <= MAX_DISTANCE);
+    }
+
+    #[test]
+    fn tells_different_images_apart() {
+        let first = perceptual_hash(&stripes(400, 300, false, ImageFormat::Png)).unwrap();
+        let second = perceptual_hash(&stripes(400, 300, true, ImageFormat::Png)).unwrap();
+
+        assert!(distance(first, second) > MAX_DISTANCE);
+    }
+
+    #[test]
+    fn rejects_non_images() {
+        assert!(perceptual_hash(b"not an image").is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use serenity::all::Http;
+use tokio::time::Duration;
+use tracing::warn;
+
+use super::BouncerJob;
+
+use crate::bot::{
+    database::{AttachmentPurge, Interview},
+    id_verification, BouncerState,
+};
+
+/// Retries purging the ID verification attachments of interviews whose purge failed when
+/// they ended.
+pub struct Job {
+    pub check_interval: Duration,
+}
+
+impl BouncerJob for Job {
+    const JOB_NAME: &'static str = "attachment_purges";
+
+    fn interval(&self) -> Duration {
+        self.check_interval
+    }
+
+    async fn run(&self, http: &Http, state: &BouncerState) -> anyhow::Result<()> {
+        for purge in AttachmentPurge::fetch_all(&state.database).await? {
+            let Some(interview) = Interview::fetch(&state.database, purge.interview_id).await?
+            else {
+                warn!(
+                    "interview `{}` of a pending purge doesn't exist, clearing it...",
+                    purge.interview_id
+                );
+                AttachmentPurge::delete(&state.database, purge.interview_id).await?;
+                continue;
+            };
+
+            id_verification::retry_purge_or_warn(http, state, &interview).await;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use std::{future::Future, sync::Arc};
+
+use serenity::all::Http;
+use tokio::{
+    sync::RwLock,
+    time::{self, Duration, MissedTickBehavior},
+};
+use tracing::{error, trace};
+
+use super::BouncerState;
+
+pub mod attachment_purges;
+pub mod reconcile;
+pub mod stale_interviews;
+
+pub trait BouncerJob: Send + Sync + 'static {
+    const JOB_NAME: &'static str;
+
+    /// How often the job runs.
+    fn interval(&self) -> Duration;
+
+    fn run(
+        &self,
+        http: &Http,
+        state: &BouncerState,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+/// Runs a job periodically in the background for the lifetime of the bot.
+///
+/// Ticks are skipped until the context is populated, since jobs rely on it.
+pub fn spawn<J: BouncerJob>(job: J, http: Arc<Http>, state: Arc<RwLock<BouncerState>>) {
+    tokio::spawn(async move {
+        let mut interval = time::interval(job.interval());
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            let state = state.read().await;
+            if !state.context.is_populated() {
+                trace!(
+                    "context is not populated yet, skipping the `{}` job...",
+                    J::JOB_NAME
+                );
+                continue;
+            }
+
+            trace!("running the `{}` job...", J::JOB_NAME);
+            match job.run(&http, &state).await {
+                Ok(()) => trace!("ran the `{}` job", J::JOB_NAME),
+                Err(error) => error!(
+                    "an error occurred while running the `{}` job: {error:#?}",
+                    J::JOB_NAME
+                ),
+            }
+        }
+    });
+}
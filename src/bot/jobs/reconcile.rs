@@ -0,0 +1,37 @@
+use serenity::all::{CreateMessage, Http};
+use tokio::time::Duration;
+
+use super::BouncerJob;
+
+use crate::bot::{reconcile, BouncerState};
+
+/// Compares member roles with the database records and reports the drift to the marks
+/// channel, fixing it is left to `/reconcile`.
+pub struct Job {
+    pub check_interval: Duration,
+}
+
+impl BouncerJob for Job {
+    const JOB_NAME: &'static str = "reconcile";
+
+    fn interval(&self) -> Duration {
+        self.check_interval
+    }
+
+    async fn run(&self, http: &Http, state: &BouncerState) -> anyhow::Result<()> {
+        let report = reconcile::run(http, state, None).await?;
+        if report.drifts.is_empty() {
+            return Ok(());
+        }
+
+        state
+            .context
+            .channels
+            .interview_marks
+            .id
+            .send_message(http, CreateMessage::new().content(report.summary()))
+            .await?;
+
+        Ok(())
+    }
+}
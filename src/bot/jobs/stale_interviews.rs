@@ -0,0 +1,58 @@
+use serenity::all::{CreateMessage, Http, Mentionable, UserId};
+use tokio::time::Duration;
+use tracing::warn;
+
+use super::BouncerJob;
+
+use crate::bot::{
+    database::{self, Interview},
+    lifecycle, BouncerState,
+};
+
+/// Reverts interviews that have been ongoing for too long back to pending.
+pub struct Job {
+    pub stale_after_minutes: u64,
+    pub check_interval: Duration,
+}
+
+impl BouncerJob for Job {
+    const JOB_NAME: &'static str = "stale_interviews";
+
+    fn interval(&self) -> Duration {
+        self.check_interval
+    }
+
+    async fn run(&self, http: &Http, state: &BouncerState) -> anyhow::Result<()> {
+        let stale_after_minutes = i64::try_from(self.stale_after_minutes)?;
+        let interviews = Interview::fetch_stale(&state.database, stale_after_minutes).await?;
+
+        for interview in interviews {
+            if let Err(error) = lifecycle::revert_interview(http, state, &interview).await {
+                warn!(
+                    "could not revert the stale interview of `{}`: {error}",
+                    interview.user_id
+                );
+                continue;
+            }
+
+            let user_id = UserId::new(database::from_id(interview.user_id));
+            let interviewer_id = UserId::new(database::from_id(interview.interviewer_id));
+            if let Err(error) = interviewer_id
+                .direct_message(
+                    http,
+                    CreateMessage::new().content(format!(
+                        "Your interview with {} was ongoing for more than {} minutes, so it \
+                         was reverted back to pending.",
+                        user_id.mention(),
+                        self.stale_after_minutes
+                    )),
+                )
+                .await
+            {
+                warn!("could not notify the interviewer `{interviewer_id}` of a stale interview: {error}");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1238 @@
+//! Interview lifecycle operations shared between commands and other interactions.
+
+use serenity::{
+    all::{
+        ChannelId, ChannelType, CreateChannel, EditChannel, Http, Mentionable, PermissionOverwrite,
+        PermissionOverwriteType, Permissions, RoleId, UserId,
+    },
+    http::{HttpError, StatusCode},
+};
+use sqlx::SqliteConnection;
+use tracing::{debug, info, warn};
+
+use super::{
+    context::Tier,
+    database::{
+        self, Application, AuditAction, AuditEntry, Interview, InterviewOutcome, User, UserStatus,
+    },
+    id_verification, marks, questionnaire,
+    status::{self, InvalidTransition, Transition},
+    transcript, BouncerState,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    /// The user is already being interviewed.
+    AlreadyOngoing,
+    /// The user is already approved for a tier ranked the same or higher than the one with
+    /// the given label.
+    NotAnUpgrade(String),
+    /// The user is not being interviewed at the moment.
+    NotOngoing,
+    /// The user is not waiting for an interview.
+    NotPending,
+    /// The user is approved already.
+    AlreadyApproved,
+    /// The user is rejected already.
+    AlreadyRejected,
+    /// The user has not been rejected.
+    NotRejected,
+    /// The user applied already and is waiting for an interview.
+    AlreadyApplied,
+    /// The user is not waiting for an interview, so they cannot apply.
+    CannotApply,
+    /// The user is claimed by another interviewer.
+    ClaimedByOther(UserId),
+    /// The tier with the given name is not configured.
+    UnknownTier(String),
+    /// The interviewer lacks the roles required to interview for the tier with the given
+    /// label.
+    NotTierInterviewer(String),
+    /// The status of the user cannot change as requested.
+    InvalidTransition(InvalidTransition),
+
+    Database(sqlx::Error),
+    Discord(serenity::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadyOngoing => write!(f, "This user is already being interviewed."),
+            Self::NotAnUpgrade(label) => write!(
+                f,
+                "This user is already verified for a tier at least as high as `{label}`."
+            ),
+            Self::NotOngoing => write!(f, "This user is not being interviewed."),
+            Self::NotPending => write!(f, "This user is not waiting for an interview."),
+            Self::AlreadyApproved => write!(f, "This user is already approved."),
+            Self::AlreadyRejected => write!(f, "This user is already rejected."),
+            Self::NotRejected => write!(f, "This user has not been rejected."),
+            Self::AlreadyApplied => write!(
+                f,
+                "You have already applied, an interviewer will reach out to you soon."
+            ),
+            Self::CannotApply => write!(f, "Only users waiting for an interview can apply."),
+            Self::ClaimedByOther(interviewer_id) => write!(
+                f,
+                "This user is already claimed by {}.",
+                interviewer_id.mention()
+            ),
+            Self::UnknownTier(name) => write!(f, "The `{name}` tier is not configured."),
+            Self::NotTierInterviewer(label) => {
+                write!(f, "You cannot interview for the `{label}` tier.")
+            }
+            Self::InvalidTransition(error) => write!(f, "{error}"),
+            Self::Database(error) => write!(f, "database error: {error}"),
+            Self::Discord(error) => write!(f, "Discord error: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<sqlx::Error> for Error {
+    fn from(error: sqlx::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+impl From<InvalidTransition> for Error {
+    fn from(error: InvalidTransition) -> Self {
+        Self::InvalidTransition(error)
+    }
+}
+
+impl From<serenity::Error> for Error {
+    fn from(error: serenity::Error) -> Self {
+        Self::Discord(error)
+    }
+}
+
+impl Error {
+    /// Whether the error is caused by the user input and can be shown to the user as is.
+    pub const fn is_user_facing(&self) -> bool {
+        !matches!(self, Self::Database(_) | Self::Discord(_))
+    }
+}
+
+/// Finds the tier with the given name, making sure an interviewer with the given roles can
+/// interview for it.
+pub fn resolve_tier<'s>(
+    state: &'s BouncerState,
+    name: &str,
+    interviewer_role_ids: &[RoleId],
+) -> Result<&'s Tier, Error> {
+    let tier = state
+        .context
+        .tier(name)
+        .ok_or_else(|| Error::UnknownTier(name.to_string()))?;
+    if !tier.can_interview(interviewer_role_ids) {
+        return Err(Error::NotTierInterviewer(tier.label.clone()));
+    }
+
+    Ok(tier)
+}
+
+/// Starts an interview of `user_id` by `interviewer_id`, moving the user to `ongoing`,
+/// swapping their pending interview role to the ongoing one and opening a private interview
+/// channel. Approved users can only be interviewed for a higher ranked tier, which upgrades
+/// them if approved. Returns the ID of the interview channel.
+pub async fn start_interview(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    interviewer_id: UserId,
+    tier: &Tier,
+) -> Result<ChannelId, Error> {
+    let database_user_id = database::to_id(user_id.get());
+    let database_interviewer_id = database::to_id(interviewer_id.get());
+
+    ensure_can_start(
+        &mut *state.database.acquire().await?,
+        state,
+        database_user_id,
+        database_interviewer_id,
+        tier,
+    )
+    .await?;
+
+    //* Users being upgraded keep the role of their previous tier until the interview ends.
+    let removed_role_ids = [state.context.roles.pending_interview.id];
+    let added_role_ids = [state.context.roles.ongoing_interview.id];
+    swap_roles(
+        http,
+        state,
+        user_id,
+        &removed_role_ids,
+        &added_role_ids,
+        "Interview started.",
+    )
+    .await?;
+    let channel_id = match open_channel(http, state, user_id, interviewer_id).await {
+        Ok(channel_id) => channel_id,
+        Err(error) => {
+            undo_swap_roles_or_warn(http, state, user_id, &removed_role_ids, &added_role_ids).await;
+            return Err(error.into());
+        }
+    };
+
+    let interview_id = match record_start(
+        state,
+        database_user_id,
+        database_interviewer_id,
+        tier,
+        channel_id,
+    )
+    .await
+    {
+        Ok(interview_id) => interview_id,
+        Err(error) => {
+            if let Err(error) = http
+                .delete_channel(channel_id, Some("Interview could not be recorded."))
+                .await
+            {
+                warn!("could not delete the interview channel `{channel_id}`: {error}");
+            }
+            undo_swap_roles_or_warn(http, state, user_id, &removed_role_ids, &added_role_ids).await;
+            return Err(error);
+        }
+    };
+
+    marks::sync_or_warn(http, state, user_id).await;
+    questionnaire::ask_next_or_warn(http, state, interview_id, user_id, channel_id, tier).await;
+
+    info!(
+        "started a `{}` interview of `{user_id}` by `{interviewer_id}`",
+        tier.name
+    );
+
+    Ok(channel_id)
+}
+
+/// Records the start of an interview once its roles are swapped and its channel is open.
+/// Returns the ID of the interview.
+async fn record_start(
+    state: &BouncerState,
+    user_id: i64,
+    interviewer_id: i64,
+    tier: &Tier,
+    channel_id: ChannelId,
+) -> Result<i64, Error> {
+    let mut transaction = state.database.begin().await?;
+
+    let (transition, previous_tier) =
+        ensure_can_start(&mut transaction, state, user_id, interviewer_id, tier).await?;
+    User::set_status(&mut *transaction, user_id, transition).await?;
+    AuditEntry::record(
+        &mut *transaction,
+        AuditAction::InterviewStarted,
+        Some(interviewer_id),
+        user_id,
+        Some(transition),
+        Some(&format!("Interview for the `{}` tier.", tier.label)),
+    )
+    .await?;
+    let interview_id = Interview::insert(
+        &mut *transaction,
+        user_id,
+        interviewer_id,
+        &tier.name,
+        previous_tier.as_deref(),
+    )
+    .await?;
+    Interview::set_channel_id(
+        &mut *transaction,
+        interview_id,
+        database::to_id(channel_id.get()),
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(interview_id)
+}
+
+/// Approves the ongoing interview of `user_id`, granting them the role of the tier of their
+/// interview in place of any other tier role. The decider needs the roles required to
+/// interview for that tier. Returns the label of the approved tier.
+pub async fn approve_interview(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    decider_id: UserId,
+    decider_role_ids: &[RoleId],
+) -> Result<String, Error> {
+    let database_user_id = database::to_id(user_id.get());
+
+    let (_, interview, tier) = ensure_approvable(
+        &mut *state.database.acquire().await?,
+        state,
+        database_user_id,
+    )
+    .await?;
+    if !tier.can_interview(decider_role_ids) {
+        return Err(Error::NotTierInterviewer(tier.label.clone()));
+    }
+
+    let mut removed_role_ids = vec![
+        state.context.roles.pending_interview.id,
+        state.context.roles.ongoing_interview.id,
+    ];
+    removed_role_ids.extend(other_tier_role_ids(state, tier));
+    let added_role_ids = [tier.role.id];
+    swap_roles_if_member(
+        http,
+        state,
+        user_id,
+        &removed_role_ids,
+        &added_role_ids,
+        "Interview approved.",
+    )
+    .await?;
+
+    if let Err(error) = record_approval(state, database_user_id, interview.id, decider_id).await {
+        undo_swap_roles_or_warn(http, state, user_id, &removed_role_ids, &added_role_ids).await;
+        return Err(error);
+    }
+    marks::sync_or_warn(http, state, user_id).await;
+
+    close_channel_or_warn(http, state, &interview, user_id).await;
+
+    info!(
+        "approved the `{}` interview of `{user_id}` by `{decider_id}`",
+        tier.name
+    );
+
+    Ok(tier.label.clone())
+}
+
+/// Records the approval of an interview once the roles of the user are swapped.
+async fn record_approval(
+    state: &BouncerState,
+    user_id: i64,
+    interview_id: i64,
+    decider_id: UserId,
+) -> Result<(), Error> {
+    let mut transaction = state.database.begin().await?;
+
+    let (user, interview, tier) = ensure_approvable(&mut transaction, state, user_id).await?;
+    if interview.id != interview_id {
+        return Err(Error::NotOngoing);
+    }
+    let transition = status::transition(Some(user.status), UserStatus::Approved)?;
+    let database_decider_id = database::to_id(decider_id.get());
+    User::decide(
+        &mut *transaction,
+        user_id,
+        transition,
+        database_decider_id,
+        None,
+    )
+    .await?;
+    AuditEntry::record(
+        &mut *transaction,
+        AuditAction::Approved,
+        Some(database_decider_id),
+        user_id,
+        Some(transition),
+        Some(&format!("Approved for the `{}` tier.", tier.label)),
+    )
+    .await?;
+    Interview::finish(
+        &mut *transaction,
+        interview.id,
+        InterviewOutcome::Approved,
+        None,
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// Rejects `user_id` for the given reason, either during their interview or while they
+/// are still waiting for one. During an interview, the decider needs the roles required to
+/// interview for its tier. Users being upgraded stay approved for their previous tier.
+pub async fn reject_interview(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    decider_id: UserId,
+    decider_role_ids: &[RoleId],
+    reason: &str,
+) -> Result<(), Error> {
+    let database_user_id = database::to_id(user_id.get());
+
+    let (_, interview) =
+        ensure_rejectable(&mut *state.database.acquire().await?, database_user_id).await?;
+    if let Some(interview) = &interview {
+        let tier = state
+            .context
+            .tier(&interview.tier)
+            .ok_or_else(|| Error::UnknownTier(interview.tier.clone()))?;
+        if !tier.can_interview(decider_role_ids) {
+            return Err(Error::NotTierInterviewer(tier.label.clone()));
+        }
+    }
+
+    let removed_role_ids = [
+        state.context.roles.pending_interview.id,
+        state.context.roles.ongoing_interview.id,
+    ];
+    swap_roles_if_member(
+        http,
+        state,
+        user_id,
+        &removed_role_ids,
+        &[],
+        "Interview rejected.",
+    )
+    .await?;
+
+    if let Err(error) = record_rejection(
+        state,
+        database_user_id,
+        interview.as_ref().map(|interview| interview.id),
+        decider_id,
+        reason,
+    )
+    .await
+    {
+        undo_swap_roles_or_warn(http, state, user_id, &removed_role_ids, &[]).await;
+        return Err(error);
+    }
+    marks::sync_or_warn(http, state, user_id).await;
+
+    if let Some(interview) = interview {
+        close_channel_or_warn(http, state, &interview, user_id).await;
+    }
+
+    info!("rejected the interview of `{user_id}` by `{decider_id}`: {reason}");
+
+    Ok(())
+}
+
+/// Records the rejection of a user once their interview roles are removed.
+async fn record_rejection(
+    state: &BouncerState,
+    user_id: i64,
+    interview_id: Option<i64>,
+    decider_id: UserId,
+    reason: &str,
+) -> Result<(), Error> {
+    let mut transaction = state.database.begin().await?;
+
+    let (status, interview) = ensure_rejectable(&mut transaction, user_id).await?;
+    if interview.as_ref().map(|interview| interview.id) != interview_id {
+        return Err(Error::NotOngoing);
+    }
+
+    if let Some(interview) = &interview {
+        Interview::finish(
+            &mut *transaction,
+            interview.id,
+            InterviewOutcome::Rejected,
+            Some(reason),
+        )
+        .await?;
+    }
+    let database_decider_id = database::to_id(decider_id.get());
+    let transition = if interview
+        .as_ref()
+        .is_some_and(|interview| interview.previous_tier.is_some())
+    {
+        //* The previous approval stays the decision about the user.
+        let transition = status::transition(Some(status), UserStatus::Approved)?;
+        User::set_status(&mut *transaction, user_id, transition).await?;
+        transition
+    } else {
+        let transition = status::transition(Some(status), UserStatus::Rejected)?;
+        User::decide(
+            &mut *transaction,
+            user_id,
+            transition,
+            database_decider_id,
+            Some(reason),
+        )
+        .await?;
+        transition
+    };
+    AuditEntry::record(
+        &mut *transaction,
+        AuditAction::Rejected,
+        Some(database_decider_id),
+        user_id,
+        Some(transition),
+        Some(reason),
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// Reverts a stale ongoing interview, moving the user back to `pending` and swapping their
+/// ongoing interview role back to the pending one, or back to `approved` if it was an
+/// upgrade.
+pub async fn revert_interview(
+    http: &Http,
+    state: &BouncerState,
+    interview: &Interview,
+) -> Result<(), Error> {
+    let user_id = UserId::new(database::from_id(interview.user_id));
+
+    ensure_revertable(&mut *state.database.acquire().await?, interview).await?;
+
+    let removed_role_ids = [state.context.roles.ongoing_interview.id];
+    let (status, added_role_ids) = if interview.previous_tier.is_some() {
+        (UserStatus::Approved, vec![])
+    } else {
+        (
+            UserStatus::Pending,
+            vec![state.context.roles.pending_interview.id],
+        )
+    };
+    swap_roles_if_member(
+        http,
+        state,
+        user_id,
+        &removed_role_ids,
+        &added_role_ids,
+        "Interview timed out.",
+    )
+    .await?;
+
+    if let Err(error) = record_reversion(state, interview, status).await {
+        undo_swap_roles_or_warn(http, state, user_id, &removed_role_ids, &added_role_ids).await;
+        return Err(error);
+    }
+    marks::sync_or_warn(http, state, user_id).await;
+
+    close_channel_or_warn(http, state, interview, user_id).await;
+
+    info!(
+        "reverted the stale interview of `{user_id}` by `{}`",
+        interview.interviewer_id
+    );
+
+    Ok(())
+}
+
+/// Records the reversion of a stale interview once the roles of the user are swapped back.
+async fn record_reversion(
+    state: &BouncerState,
+    interview: &Interview,
+    status: UserStatus,
+) -> Result<(), Error> {
+    let mut transaction = state.database.begin().await?;
+
+    let user = ensure_revertable(&mut transaction, interview).await?;
+    Interview::finish(
+        &mut *transaction,
+        interview.id,
+        InterviewOutcome::Reverted,
+        Some("Interview timed out."),
+    )
+    .await?;
+    let transition = status::transition(Some(user.status), status)?;
+    User::set_status(&mut *transaction, interview.user_id, transition).await?;
+    AuditEntry::record(
+        &mut *transaction,
+        AuditAction::Reverted,
+        None,
+        interview.user_id,
+        Some(transition),
+        Some("Interview timed out."),
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// Records a verified role granted by hand as an approval of `user_id` by the moderator
+/// who granted it, ending their interview if they were being interviewed.
+pub async fn record_manual_approval(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    moderator_id: UserId,
+    tier: &Tier,
+) -> Result<(), Error> {
+    let database_user_id = database::to_id(user_id.get());
+
+    ensure_manually_approvable(&mut *state.database.acquire().await?, database_user_id).await?;
+
+    let mut removed_role_ids = vec![
+        state.context.roles.pending_interview.id,
+        state.context.roles.ongoing_interview.id,
+    ];
+    removed_role_ids.extend(other_tier_role_ids(state, tier));
+    swap_roles(
+        http,
+        state,
+        user_id,
+        &removed_role_ids,
+        &[],
+        "Verification role granted manually.",
+    )
+    .await?;
+
+    let interview = match record_manual_grant(state, database_user_id, moderator_id, tier).await {
+        Ok(interview) => interview,
+        Err(error) => {
+            undo_swap_roles_or_warn(http, state, user_id, &removed_role_ids, &[]).await;
+            return Err(error);
+        }
+    };
+    marks::sync_or_warn(http, state, user_id).await;
+
+    if let Some(interview) = interview {
+        close_channel_or_warn(http, state, &interview, user_id).await;
+    }
+
+    info!(
+        "recorded the manual `{}` approval of `{user_id}` by `{moderator_id}`",
+        tier.name
+    );
+
+    Ok(())
+}
+
+/// Records a manually granted verification role as an approval once the other roles of the
+/// user are removed. Returns the interview it ended, if the user was being interviewed.
+async fn record_manual_grant(
+    state: &BouncerState,
+    user_id: i64,
+    moderator_id: UserId,
+    tier: &Tier,
+) -> Result<Option<Interview>, Error> {
+    let database_moderator_id = database::to_id(moderator_id.get());
+
+    let mut transaction = state.database.begin().await?;
+
+    let (status, interview) = ensure_manually_approvable(&mut transaction, user_id).await?;
+    let transition = status::transition(status, UserStatus::Approved)?;
+    User::decide(
+        &mut *transaction,
+        user_id,
+        transition,
+        database_moderator_id,
+        Some("Verification role granted manually."),
+    )
+    .await?;
+    AuditEntry::record(
+        &mut *transaction,
+        AuditAction::Override,
+        Some(database_moderator_id),
+        user_id,
+        Some(transition),
+        Some(&format!(
+            "Role of the `{}` tier granted manually, recorded as an approval.",
+            tier.label
+        )),
+    )
+    .await?;
+    if let Some(interview) = &interview {
+        Interview::finish(
+            &mut *transaction,
+            interview.id,
+            InterviewOutcome::Reverted,
+            Some("Verification role granted manually."),
+        )
+        .await?;
+    }
+    //* The tier of the approval lives on the interview, so one is recorded for the grant.
+    let previous_tier = Interview::latest_approved(&mut *transaction, user_id)
+        .await?
+        .map(|interview| interview.tier);
+    let interview_id = Interview::insert(
+        &mut *transaction,
+        user_id,
+        database_moderator_id,
+        &tier.name,
+        previous_tier.as_deref(),
+    )
+    .await?;
+    Interview::finish(
+        &mut *transaction,
+        interview_id,
+        InterviewOutcome::Approved,
+        Some("Verification role granted manually."),
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(interview)
+}
+
+/// Reopens a rejected `user_id` on appeal, moving them back to `pending` with the pending
+/// interview role so they can be interviewed again.
+pub async fn reopen_user(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    moderator_id: UserId,
+) -> Result<(), Error> {
+    let database_user_id = database::to_id(user_id.get());
+
+    ensure_rejected(&mut *state.database.acquire().await?, database_user_id).await?;
+
+    let added_role_ids = [state.context.roles.pending_interview.id];
+    swap_roles_if_member(
+        http,
+        state,
+        user_id,
+        &[],
+        &added_role_ids,
+        "Rejection appealed.",
+    )
+    .await?;
+
+    if let Err(error) = record_reopening(state, database_user_id, moderator_id).await {
+        undo_swap_roles_or_warn(http, state, user_id, &[], &added_role_ids).await;
+        return Err(error);
+    }
+    marks::sync_or_warn(http, state, user_id).await;
+
+    info!("reopened `{user_id}` on appeal by `{moderator_id}`");
+
+    Ok(())
+}
+
+/// Records the reopening of a rejected user once they hold the pending interview role.
+async fn record_reopening(
+    state: &BouncerState,
+    user_id: i64,
+    moderator_id: UserId,
+) -> Result<(), Error> {
+    let mut transaction = state.database.begin().await?;
+
+    let user = ensure_rejected(&mut transaction, user_id).await?;
+    let transition = status::transition(Some(user.status), UserStatus::Pending)?;
+    User::set_status(&mut *transaction, user_id, transition).await?;
+    AuditEntry::record(
+        &mut *transaction,
+        AuditAction::Reopened,
+        Some(database::to_id(moderator_id.get())),
+        user_id,
+        Some(transition),
+        Some("Rejection appealed."),
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// Checks whether `user_id` can submit an application, before they fill the form in.
+pub async fn can_apply(state: &BouncerState, user_id: UserId) -> Result<(), Error> {
+    let mut connection = state.database.acquire().await?;
+    ensure_can_apply(&mut connection, database::to_id(user_id.get())).await?;
+
+    Ok(())
+}
+
+/// Records the application of `user_id`, placing them into the interview queue as
+/// `pending` if they are new.
+pub async fn submit_application(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    age: i64,
+    source: &str,
+    reason: &str,
+) -> Result<(), Error> {
+    let database_user_id = database::to_id(user_id.get());
+
+    ensure_can_apply(&mut *state.database.acquire().await?, database_user_id).await?;
+
+    let added_role_ids = [state.context.roles.pending_interview.id];
+    swap_roles(
+        http,
+        state,
+        user_id,
+        &[],
+        &added_role_ids,
+        "Applied for an interview.",
+    )
+    .await?;
+
+    if let Err(error) = record_application(state, database_user_id, age, source, reason).await {
+        undo_swap_roles_or_warn(http, state, user_id, &[], &added_role_ids).await;
+        return Err(error);
+    }
+    marks::sync_or_warn(http, state, user_id).await;
+
+    info!("`{user_id}` applied for an interview");
+
+    Ok(())
+}
+
+/// Records an application once its user holds the pending interview role.
+async fn record_application(
+    state: &BouncerState,
+    user_id: i64,
+    age: i64,
+    source: &str,
+    reason: &str,
+) -> Result<(), Error> {
+    let mut transaction = state.database.begin().await?;
+
+    let user = ensure_can_apply(&mut transaction, user_id).await?;
+    let transition = match user {
+        Some(_) => None,
+        None => {
+            let transition = status::transition(None, UserStatus::Pending)?;
+            User::set_status(&mut *transaction, user_id, transition).await?;
+            Some(transition)
+        }
+    };
+    Application::insert(&mut *transaction, user_id, age, source, reason).await?;
+    AuditEntry::record(
+        &mut *transaction,
+        AuditAction::Applied,
+        Some(user_id),
+        user_id,
+        transition,
+        None,
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// Claims a pending `user_id` for `interviewer_id`, so other interviewers know who is going
+/// to interview them.
+pub async fn claim_user(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    interviewer_id: UserId,
+) -> Result<(), Error> {
+    let database_user_id = database::to_id(user_id.get());
+    let database_interviewer_id = database::to_id(interviewer_id.get());
+
+    ensure_claimable(
+        &mut *state.database.acquire().await?,
+        database_user_id,
+        database_interviewer_id,
+    )
+    .await?;
+
+    let mut transaction = state.database.begin().await?;
+    if !User::claim(&mut *transaction, database_user_id, database_interviewer_id).await? {
+        //* Another interviewer claimed them or they moved on since the check above.
+        ensure_claimable(&mut transaction, database_user_id, database_interviewer_id).await?;
+        return Err(Error::NotPending);
+    }
+    AuditEntry::record(
+        &mut *transaction,
+        AuditAction::Claimed,
+        Some(database_interviewer_id),
+        database_user_id,
+        None,
+        None,
+    )
+    .await?;
+    transaction.commit().await?;
+
+    marks::sync_or_warn(http, state, user_id).await;
+
+    info!("`{user_id}` is claimed by `{interviewer_id}`");
+
+    Ok(())
+}
+
+/// Makes sure `user_id` is pending and not claimed by another interviewer than
+/// `interviewer_id`.
+async fn ensure_claimable(
+    connection: &mut SqliteConnection,
+    user_id: i64,
+    interviewer_id: i64,
+) -> Result<(), Error> {
+    match User::fetch(&mut *connection, user_id).await? {
+        Some(user) if user.status == UserStatus::Pending => {
+            ensure_not_claimed_by_other(&user, interviewer_id)
+        }
+        _ => Err(Error::NotPending),
+    }
+}
+
+fn ensure_not_claimed_by_other(user: &User, interviewer_id: i64) -> Result<(), Error> {
+    match user.claimed_by {
+        Some(claimed_by) if claimed_by != interviewer_id => Err(Error::ClaimedByOther(
+            UserId::new(database::from_id(claimed_by)),
+        )),
+        _ => Ok(()),
+    }
+}
+
+//* The checks below run once before Discord is changed and once more in the transaction
+//* recording the change, so nothing changed in between goes unnoticed.
+
+/// Makes sure `user_id` can be interviewed by `interviewer_id` for `tier`. Returns the
+/// transition of their status and the tier they are being upgraded from, if any.
+async fn ensure_can_start(
+    connection: &mut SqliteConnection,
+    state: &BouncerState,
+    user_id: i64,
+    interviewer_id: i64,
+    tier: &Tier,
+) -> Result<(Transition, Option<String>), Error> {
+    let user = User::fetch(&mut *connection, user_id).await?;
+    let mut previous_tier = None;
+    if let Some(user) = &user {
+        match user.status {
+            UserStatus::Ongoing => return Err(Error::AlreadyOngoing),
+            UserStatus::Approved => {
+                previous_tier = Interview::latest_approved(&mut *connection, user_id)
+                    .await?
+                    .map(|interview| interview.tier);
+                let previous_rank = previous_tier
+                    .as_deref()
+                    .and_then(|name| state.context.tier(name))
+                    .map(|previous_tier| previous_tier.rank);
+                if previous_rank.is_some_and(|rank| rank >= tier.rank) {
+                    return Err(Error::NotAnUpgrade(tier.label.clone()));
+                }
+            }
+            UserStatus::Pending | UserStatus::Rejected => {}
+        }
+        ensure_not_claimed_by_other(user, interviewer_id)?;
+    }
+    let transition = status::transition(user.map(|user| user.status), UserStatus::Ongoing)?;
+
+    Ok((transition, previous_tier))
+}
+
+async fn ensure_ongoing(connection: &mut SqliteConnection, user_id: i64) -> Result<User, Error> {
+    match User::fetch(&mut *connection, user_id).await? {
+        Some(user) if user.status == UserStatus::Ongoing => Ok(user),
+        _ => Err(Error::NotOngoing),
+    }
+}
+
+/// Makes sure `user_id` is being interviewed for a configured tier. Returns their record,
+/// their interview and its tier.
+async fn ensure_approvable<'s>(
+    connection: &mut SqliteConnection,
+    state: &'s BouncerState,
+    user_id: i64,
+) -> Result<(User, Interview, &'s Tier), Error> {
+    let user = ensure_ongoing(&mut *connection, user_id).await?;
+    let interview = Interview::latest(&mut *connection, user_id)
+        .await?
+        .ok_or(Error::NotOngoing)?;
+    let tier = state
+        .context
+        .tier(&interview.tier)
+        .ok_or_else(|| Error::UnknownTier(interview.tier.clone()))?;
+
+    Ok((user, interview, tier))
+}
+
+/// Makes sure `user_id` is being interviewed or waiting for an interview. Returns their
+/// status and their interview, if they are being interviewed.
+async fn ensure_rejectable(
+    connection: &mut SqliteConnection,
+    user_id: i64,
+) -> Result<(UserStatus, Option<Interview>), Error> {
+    match User::fetch(&mut *connection, user_id).await? {
+        Some(user) if user.status == UserStatus::Ongoing => Ok((
+            user.status,
+            Interview::latest(&mut *connection, user_id).await?,
+        )),
+        Some(user) if user.status == UserStatus::Pending => Ok((user.status, None)),
+        Some(user) if user.status == UserStatus::Approved => Err(Error::AlreadyApproved),
+        Some(_) => Err(Error::AlreadyRejected),
+        None => Err(Error::NotPending),
+    }
+}
+
+/// Makes sure the user of an interview is still being interviewed in it. Returns their
+/// record.
+async fn ensure_revertable(
+    connection: &mut SqliteConnection,
+    interview: &Interview,
+) -> Result<User, Error> {
+    let user = ensure_ongoing(&mut *connection, interview.user_id).await?;
+    let latest = Interview::latest(&mut *connection, interview.user_id).await?;
+    if latest.map(|latest| latest.id) != Some(interview.id) {
+        return Err(Error::NotOngoing);
+    }
+
+    Ok(user)
+}
+
+/// Makes sure `user_id` can be approved by hand. Returns their status and their
+/// interview, if they are being interviewed.
+async fn ensure_manually_approvable(
+    connection: &mut SqliteConnection,
+    user_id: i64,
+) -> Result<(Option<UserStatus>, Option<Interview>), Error> {
+    let status = User::fetch(&mut *connection, user_id)
+        .await?
+        .map(|user| user.status);
+    status::transition(status, UserStatus::Approved)?;
+    let interview = match status {
+        Some(UserStatus::Ongoing) => Interview::latest(&mut *connection, user_id).await?,
+        _ => None,
+    };
+
+    Ok((status, interview))
+}
+
+async fn ensure_rejected(connection: &mut SqliteConnection, user_id: i64) -> Result<User, Error> {
+    match User::fetch(&mut *connection, user_id).await? {
+        Some(user) if user.status == UserStatus::Rejected => Ok(user),
+        _ => Err(Error::NotRejected),
+    }
+}
+
+/// Makes sure `user_id` can submit an application, which is the case if they are new or
+/// pending without an application since their last decision. Returns their record.
+async fn ensure_can_apply(
+    connection: &mut sqlx::SqliteConnection,
+    user_id: i64,
+) -> Result<Option<User>, Error> {
+    let user = User::fetch(&mut *connection, user_id).await?;
+    if user
+        .as_ref()
+        .is_some_and(|user| user.status != UserStatus::Pending)
+    {
+        return Err(Error::CannotApply);
+    }
+
+    if let Some(application) = Application::latest(&mut *connection, user_id).await? {
+        //* Users reopened on appeal can apply again.
+        let decided_since = user
+            .as_ref()
+            .and_then(|user| user.decision_date)
+            .is_some_and(|decision_date| decision_date > application.submission_date);
+        if !decided_since {
+            return Err(Error::AlreadyApplied);
+        }
+    }
+
+    Ok(user)
+}
+
+/// The roles of every tier besides the given one, which an approved user shouldn't hold.
+fn other_tier_role_ids(state: &BouncerState, tier: &Tier) -> Vec<RoleId> {
+    state
+        .context
+        .tiers
+        .iter()
+        .filter(|other| other.name != tier.name)
+        .map(|other| other.role.id)
+        .collect()
+}
+
+/// Removes and adds the given roles of a guild member.
+async fn swap_roles(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    remove: &[RoleId],
+    add: &[RoleId],
+    reason: &str,
+) -> Result<(), serenity::Error> {
+    let guild_id = state.context.guild.id;
+
+    for role_id in remove {
+        http.remove_member_role(guild_id, user_id, *role_id, Some(reason))
+            .await?;
+    }
+    for role_id in add {
+        http.add_member_role(guild_id, user_id, *role_id, Some(reason))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Whether a Discord error is caused by the member having left the guild.
+fn is_unknown_member(error: &serenity::Error) -> bool {
+    //* The configured roles are checked when the context is populated, so a missing member
+    //* is what a not found error comes from.
+    matches!(
+        error,
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response))
+            if response.status_code == StatusCode::NOT_FOUND
+    )
+}
+
+/// Swaps the roles of a member like [`swap_roles`], treating a user who left the guild as
+/// having no roles to change, so decisions about them can still be recorded.
+async fn swap_roles_if_member(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    remove: &[RoleId],
+    add: &[RoleId],
+    reason: &str,
+) -> Result<(), serenity::Error> {
+    match swap_roles(http, state, user_id, remove, add, reason).await {
+        Err(error) if is_unknown_member(&error) => {
+            debug!("`{user_id}` is not in the guild anymore, no roles to swap...");
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// Swaps back roles swapped by [`swap_roles`] when the change they were part of could not be
+/// recorded, logging instead of failing, since the original error is the one to report.
+async fn undo_swap_roles_or_warn(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    removed: &[RoleId],
+    added: &[RoleId],
+) {
+    if let Err(error) = swap_roles(
+        http,
+        state,
+        user_id,
+        added,
+        removed,
+        "Change could not be recorded.",
+    )
+    .await
+    {
+        warn!("could not swap the roles of `{user_id}` back, they may be out of sync: {error}");
+    }
+}
+
+/// Creates a private interview channel visible only to the interviewee, the interviewer
+/// and the interviewer roles.
+async fn open_channel(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    interviewer_id: UserId,
+) -> Result<ChannelId, serenity::Error> {
+    let guild_id = state.context.guild.id;
+    let allowed = Permissions::VIEW_CHANNEL
+        | Permissions::SEND_MESSAGES
+        | Permissions::READ_MESSAGE_HISTORY
+        | Permissions::ATTACH_FILES;
+
+    let mut permissions = vec![
+        PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(guild_id.everyone_role()),
+        },
+        PermissionOverwrite {
+            allow: allowed | Permissions::MANAGE_CHANNELS,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(http.get_current_user().await?.id),
+        },
+        PermissionOverwrite {
+            allow: allowed,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(user_id),
+        },
+        PermissionOverwrite {
+            allow: allowed,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(interviewer_id),
+        },
+    ];
+    permissions.extend(
+        state
+            .context
+            .roles
+            .interviewers
+            .iter()
+            .map(|role| PermissionOverwrite {
+                allow: allowed,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(role.id),
+            }),
+    );
+
+    let channel = guild_id
+        .create_channel(
+            http,
+            CreateChannel::new(format!("interview-{user_id}"))
+                .kind(ChannelType::Text)
+                .category(state.context.channels.interviews_category.id)
+                .permissions(permissions)
+                .audit_log_reason("Interview started."),
+        )
+        .await?;
+
+    Ok(channel.id)
+}
+
+/// Captures the transcript of an interview channel and purges its ID verification
+/// attachments, then locks the interviewee out of it and marks it as closed.
+async fn close_channel(
+    http: &Http,
+    state: &BouncerState,
+    interview: &Interview,
+    user_id: UserId,
+) -> Result<(), serenity::Error> {
+    let Some(channel_id) = interview.channel_id else {
+        return Ok(());
+    };
+    let channel_id = ChannelId::new(database::from_id(channel_id));
+
+    transcript::capture_or_warn(http, state, interview).await;
+    if state
+        .context
+        .tier(&interview.tier)
+        .is_some_and(|tier| tier.id_verification)
+    {
+        id_verification::purge_attachments_or_warn(http, state, interview).await;
+    }
+
+    channel_id
+        .create_permission(
+            http,
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::VIEW_CHANNEL,
+                kind: PermissionOverwriteType::Member(user_id),
+            },
+            Some("Interview ended."),
+        )
+        .await?;
+    channel_id
+        .edit(
+            http,
+            EditChannel::new()
+                .name(format!("closed-interview-{user_id}"))
+                .audit_log_reason("Interview ended."),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Closes the channel of an interview, logging instead of failing, since the decision that
+/// ended the interview is recorded already.
+async fn close_channel_or_warn(
+    http: &Http,
+    state: &BouncerState,
+    interview: &Interview,
+    user_id: UserId,
+) {
+    if let Err(error) = close_channel(http, state, interview, user_id).await {
+        warn!(
+            "could not close the channel of the interview `{}`: {error}",
+            interview.id
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Tier roles granted by hand instead of through an interview.
+
+use serenity::{
+    all::{Http, RoleId, UserId},
+    model::guild::audit_log::{Action, Change, MemberAction},
+};
+use tracing::{debug, info, warn};
+
+use super::{
+    context::Tier,
+    database::{self, AuditAction, AuditEntry, Interview, User, UserStatus},
+    lifecycle, marks, BouncerState,
+};
+
+use crate::config;
+
+/// Handles the role of `tier` being granted to `user_id`, recording or reverting the grant
+/// unless the user is approved for the tier or a higher ranked one already.
+pub async fn handle(
+    http: &Http,
+    state: &BouncerState,
+    discord_config: &config::Discord,
+    user_id: UserId,
+    tier: &Tier,
+) -> anyhow::Result<()> {
+    if is_approved_at_least(state, user_id, tier).await? {
+        return Ok(());
+    }
+    let role_id = tier.role.id;
+
+    let Some(granted_by) = find_granter(http, state, user_id, role_id).await? else {
+        warn!(
+            "could not find who granted `{role_id}` to `{user_id}` in the audit log, skipping..."
+        );
+        return Ok(());
+    };
+    //* The bot grants verified roles before committing approvals, so its own grants can
+    //* show up here before the records do.
+    if granted_by == http.get_current_user().await?.id {
+        debug!("`{role_id}` was granted to `{user_id}` by the bot, ignoring...");
+        return Ok(());
+    }
+
+    let action = match discord_config.interviews.manual_grant {
+        config::ManualGrant::Record => {
+            lifecycle::record_manual_approval(http, state, user_id, granted_by, tier).await?;
+            "Recorded as an approval."
+        }
+        config::ManualGrant::Revert => {
+            http.remove_member_role(
+                state.context.guild.id,
+                user_id,
+                role_id,
+                Some("Verification roles are granted through interviews."),
+            )
+            .await?;
+            AuditEntry::record(
+                &state.database,
+                AuditAction::Override,
+                Some(database::to_id(granted_by.get())),
+                database::to_id(user_id.get()),
+                None,
+                Some(&format!(
+                    "Role of the `{}` tier granted manually, reverted.",
+                    tier.label
+                )),
+            )
+            .await?;
+            "Reverted, since verification roles are granted through interviews."
+        }
+    };
+
+    info!(
+        "verified role `{role_id}` was granted to `{user_id}` by `{granted_by}` manually: {action}"
+    );
+    marks::log_manual_grant(http, state, user_id, role_id, granted_by, action).await?;
+
+    Ok(())
+}
+
+/// Whether `user_id` is approved for `tier` or a tier ranked above it, so granting its role
+/// changes nothing about their verification.
+async fn is_approved_at_least(
+    state: &BouncerState,
+    user_id: UserId,
+    tier: &Tier,
+) -> sqlx::Result<bool> {
+    let database_user_id = database::to_id(user_id.get());
+
+    match User::fetch(&state.database, database_user_id).await? {
+        Some(user) if user.status == UserStatus::Approved => Ok(Interview::latest_approved(
+            &state.database,
+            database_user_id,
+        )
+        .await?
+        .and_then(|interview| state.context.tier(&interview.tier))
+        .is_some_and(|approved_tier| approved_tier.rank >= tier.rank)),
+        _ => Ok(false),
+    }
+}
+
+/// How many pages of role updates are searched for a grant, since other members' role
+/// updates can push it past the first one on busy guilds.
+const AUDIT_LOG_PAGES: usize = 5;
+
+/// Looks up the moderator who most recently granted `role_id` to `user_id` in the audit log.
+///
+/// The audit log can't be filtered by target, so the entries of other members are skipped
+/// page by page.
+async fn find_granter(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    role_id: RoleId,
+) -> serenity::Result<Option<UserId>> {
+    let mut before = None;
+    for _ in 0..AUDIT_LOG_PAGES {
+        let audit_logs = state
+            .context
+            .guild
+            .id
+            .audit_logs(
+                http,
+                Some(Action::Member(MemberAction::RoleUpdate)),
+                None,
+                before,
+                None,
+            )
+            .await?;
+
+        let granter = audit_logs
+            .entries
+            .iter()
+            .filter(|entry| entry.target_id.map(|target_id| target_id.get()) == Some(user_id.get()))
+            .find(|entry| {
+                entry.changes.iter().flatten().any(|change| {
+                    matches!(
+                        change,
+                        Change::RolesAdded { new: Some(roles), .. }
+                            if roles.iter().any(|role| role.id == role_id)
+                    )
+                })
+            })
+            .map(|entry| entry.user_id);
+        if granter.is_some() {
+            return Ok(granter);
+        }
+
+        match audit_logs.entries.last() {
+            Some(entry) => before = Some(entry.id),
+            None => break,
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,322 @@
+//! What happens to a user when they post in a watched channel, based on their status.
+
+use sqlx::SqlitePool;
+
+use super::{
+    database::{AuditAction, AuditEntry, User, UserStatus},
+    status,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The user had no records and is now marked as pending.
+    Marked,
+    /// The user is already pending, their mark is refreshed.
+    Refreshed,
+    /// The user is already pending and their mark was refreshed recently, so it's left as is.
+    RecentlyRefreshed,
+    /// The user was rejected before and should be flagged to the marks channel again.
+    Flagged,
+    /// The user was rejected before and was flagged recently, so they aren't flagged again.
+    RecentlyFlagged,
+    /// The user is being interviewed or already approved.
+    Ignored,
+    /// The user has no records and isn't marked, since users aren't marked for messages.
+    Unmarked,
+}
+
+/// Records a message of a user in a watched channel and decides what to do about it. Users
+/// without records are only marked if `mark_new_users` is set. Posts of pending and
+/// rejected users are reported at most once every `report_interval_minutes`.
+pub async fn record_message(
+    database: &SqlitePool,
+    user_id: i64,
+    channel_id: i64,
+    message_id: i64,
+    mark_new_users: bool,
+    report_interval_minutes: u64,
+) -> sqlx::Result<Outcome> {
+    let status = match User::fetch(database, user_id).await? {
+        Some(user) => user.status,
+        None if !mark_new_users => return Ok(Outcome::Unmarked),
+        None => {
+            if User::mark(
+                database,
+                user_id,
+                status::MARK,
+                Some(channel_id),
+                Some(message_id),
+            )
+            .await?
+            {
+                AuditEntry::record(
+                    database,
+                    AuditAction::Marked,
+                    None,
+                    user_id,
+                    Some(status::MARK),
+                    Some(&format!("Posted in <#{channel_id}>.")),
+                )
+                .await?;
+                return Ok(Outcome::Marked);
+            }
+
+            //* Another message of the same user got them marked in the meantime.
+            UserStatus::Pending
+        }
+    };
+
+    //* Every report edits or posts a message in the marks channel, so users posting a lot
+    //* would flood it and run into rate limits otherwise.
+    match status {
+        UserStatus::Pending => {
+            if User::refresh_mark(database, user_id, report_interval_minutes).await? {
+                Ok(Outcome::Refreshed)
+            } else {
+                Ok(Outcome::RecentlyRefreshed)
+            }
+        }
+        UserStatus::Rejected => {
+            if User::refresh_mark(database, user_id, report_interval_minutes).await? {
+                Ok(Outcome::Flagged)
+            } else {
+                Ok(Outcome::RecentlyFlagged)
+            }
+        }
+        UserStatus::Ongoing | UserStatus::Approved => Ok(Outcome::Ignored),
+    }
+}
+
+/// Marks a member who just joined as pending, unless they have records already. Returns
+/// whether they were marked.
+pub async fn record_join(database: &SqlitePool, user_id: i64) -> sqlx::Result<bool> {
+    let mut transaction = database.begin().await?;
+
+    if !User::mark(&mut *transaction, user_id, status::MARK, None, None).await? {
+        return Ok(false);
+    }
+    AuditEntry::record(
+        &mut *transaction,
+        AuditAction::Marked,
+        None,
+        user_id,
+        Some(status::MARK),
+        Some("Joined the server."),
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{sqlite::SqlitePoolOptions, SqlitePool};
+
+    use super::{record_join, record_message, Outcome};
+    use crate::bot::{
+        database::{AuditAction, AuditEntry, User, UserStatus},
+        status,
+    };
+
+    async fn database() -> SqlitePool {
+        //* A single connection, since every connection gets its own in-memory database.
+        let database = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to open an in-memory database");
+        sqlx::migrate!()
+            .run(&database)
+            .await
+            .expect("failed to run the migrations");
+
+        database
+    }
+
+    #[tokio::test]
+    async fn marks_new_users() {
+        let database = database().await;
+
+        assert_eq!(
+            record_message(&database, 1, 10, 100, true, 0)
+                .await
+                .unwrap(),
+            Outcome::Marked
+        );
+
+        let user = User::fetch(&database, 1).await.unwrap().unwrap();
+        assert_eq!(user.status, UserStatus::Pending);
+        assert_eq!(user.offending_channel_id, Some(10));
+        assert_eq!(user.offending_message_id, Some(100));
+
+        let entries = AuditEntry::fetch_latest(&database, 1, 10).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, AuditAction::Marked);
+        assert_eq!(entries[0].status_before, None);
+        assert_eq!(entries[0].status_after, Some(UserStatus::Pending));
+    }
+
+    #[tokio::test]
+    async fn marks_joined_members() {
+        let database = database().await;
+
+        assert!(record_join(&database, 1).await.unwrap());
+        assert!(!record_join(&database, 1).await.unwrap());
+
+        let user = User::fetch(&database, 1).await.unwrap().unwrap();
+        assert_eq!(user.status, UserStatus::Pending);
+        assert_eq!(user.offending_message_id, None);
+
+        let entries = AuditEntry::fetch_latest(&database, 1, 10).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, AuditAction::Marked);
+        assert_eq!(entries[0].status_after, Some(UserStatus::Pending));
+    }
+
+    #[tokio::test]
+    async fn marks_every_user_separately() {
+        let database = database().await;
+
+        assert_eq!(
+            record_message(&database, 1, 10, 100, true, 0)
+                .await
+                .unwrap(),
+            Outcome::Marked
+        );
+        assert_eq!(
+            record_message(&database, 2, 10, 101, true, 0)
+                .await
+                .unwrap(),
+            Outcome::Marked
+        );
+    }
+
+    #[tokio::test]
+    async fn refreshes_pending_users() {
+        let database = database().await;
+
+        record_message(&database, 1, 10, 100, true, 0)
+            .await
+            .unwrap();
+        assert_eq!(
+            record_message(&database, 1, 11, 102, true, 0)
+                .await
+                .unwrap(),
+            Outcome::Refreshed
+        );
+
+        let user = User::fetch(&database, 1).await.unwrap().unwrap();
+        assert!(user.last_offense_date.is_some());
+        assert_eq!(user.offending_channel_id, Some(10));
+        assert_eq!(user.offending_message_id, Some(100));
+    }
+
+    #[tokio::test]
+    async fn flags_rejected_users() {
+        let database = database().await;
+
+        record_message(&database, 1, 10, 100, true, 0)
+            .await
+            .unwrap();
+        let transition =
+            status::transition(Some(UserStatus::Pending), UserStatus::Rejected).unwrap();
+        User::decide(&database, 1, transition, 2, Some("meow"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            record_message(&database, 1, 10, 103, true, 0)
+                .await
+                .unwrap(),
+            Outcome::Flagged
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_repeated_posts_once_per_interval() {
+        let database = database().await;
+
+        record_message(&database, 1, 10, 100, true, 60)
+            .await
+            .unwrap();
+        assert_eq!(
+            record_message(&database, 1, 10, 101, true, 60)
+                .await
+                .unwrap(),
+            Outcome::RecentlyRefreshed
+        );
+
+        let transition =
+            status::transition(Some(UserStatus::Pending), UserStatus::Rejected).unwrap();
+        User::decide(&database, 1, transition, 2, Some("meow"))
+            .await
+            .unwrap();
+        sqlx::query!(
+            "UPDATE users SET last_offense_date = DATETIME('now', '-2 hours') WHERE user_id = 1"
+        )
+        .execute(&database)
+        .await
+        .unwrap();
+
+        assert_eq!(
+            record_message(&database, 1, 10, 102, true, 60)
+                .await
+                .unwrap(),
+            Outcome::Flagged
+        );
+        assert_eq!(
+            record_message(&database, 1, 10, 103, true, 60)
+                .await
+                .unwrap(),
+            Outcome::RecentlyFlagged
+        );
+    }
+
+    #[tokio::test]
+    async fn only_marks_new_users_if_asked_to() {
+        let database = database().await;
+
+        assert_eq!(
+            record_message(&database, 1, 10, 100, false, 0)
+                .await
+                .unwrap(),
+            Outcome::Unmarked
+        );
+        assert!(User::fetch(&database, 1).await.unwrap().is_none());
+
+        User::mark(&database, 1, status::MARK, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            record_message(&database, 1, 10, 101, false, 0)
+                .await
+                .unwrap(),
+            Outcome::Refreshed
+        );
+    }
+
+    #[tokio::test]
+    async fn ignores_ongoing_and_approved_users() {
+        let database = database().await;
+
+        let transition = status::transition(None, UserStatus::Ongoing).unwrap();
+        User::set_status(&database, 1, transition).await.unwrap();
+        let transition = status::transition(None, UserStatus::Approved).unwrap();
+        User::set_status(&database, 2, transition).await.unwrap();
+
+        assert_eq!(
+            record_message(&database, 1, 10, 100, true, 0)
+                .await
+                .unwrap(),
+            Outcome::Ignored
+        );
+        assert_eq!(
+            record_message(&database, 2, 10, 101, true, 0)
+                .await
+                .unwrap(),
+            Outcome::Ignored
+        );
+    }
+}
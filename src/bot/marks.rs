@@ -0,0 +1,426 @@
+//! Mark messages posted to the interview marks channel, one per marked user.
+
+use serenity::all::{
+    ChannelId, Colour, CreateEmbed, CreateEmbedFooter, CreateMessage, EditMessage, Http,
+    Mentionable, Message, MessageId, RoleId, UserId,
+};
+use tracing::{debug, warn};
+
+use super::{
+    components,
+    database::{
+        self, Application, ImageHash, Interview, InterviewAnswer, InterviewOutcome, User,
+        UserStatus,
+    },
+    questionnaire, BouncerState,
+};
+
+const fn status_name(status: UserStatus) -> &'static str {
+    match status {
+        UserStatus::Pending => "Pending",
+        UserStatus::Ongoing => "Ongoing",
+        UserStatus::Approved => "Approved",
+        UserStatus::Rejected => "Rejected",
+    }
+}
+
+pub const fn outcome_name(outcome: Option<InterviewOutcome>) -> &'static str {
+    match outcome {
+        None => "ongoing",
+        Some(InterviewOutcome::Approved) => "approved",
+        Some(InterviewOutcome::Rejected) => "rejected",
+        Some(InterviewOutcome::Reverted) => "reverted",
+    }
+}
+
+/// Builds the mark embed of a user from their database record, application, interviews and
+/// the answers to their ongoing interview.
+pub fn embed<'a>(
+    state: &BouncerState,
+    user: &User,
+    application: Option<&Application>,
+    interviews: &[Interview],
+    answers: &[InterviewAnswer],
+) -> CreateEmbed<'a> {
+    let user_id = UserId::new(database::from_id(user.user_id));
+
+    let colour = match user.status {
+        UserStatus::Pending => Colour::ORANGE,
+        UserStatus::Ongoing => Colour::BLUE,
+        UserStatus::Approved => Colour::DARK_GREEN,
+        UserStatus::Rejected => Colour::RED,
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title("Marked User")
+        .colour(colour)
+        .field(
+            "User",
+            format!("{} (`{user_id}`)", user_id.mention()),
+            false,
+        )
+        .field("Status", status_name(user.status), true)
+        .field(
+            "Account Created",
+            format!("<t:{}:R>", user_id.created_at().unix_timestamp()),
+            true,
+        )
+        .field(
+            "Mark Date",
+            format!("<t:{}:F>", user.mark_date.and_utc().timestamp()),
+            true,
+        )
+        .footer(CreateEmbedFooter::new(user_id.to_string()));
+
+    if let (Some(channel_id), Some(message_id)) =
+        (user.offending_channel_id, user.offending_message_id)
+    {
+        embed = embed
+            .field("First Offending Channel", format!("<#{channel_id}>"), true)
+            .field(
+                "Message",
+                format!(
+                    "https://discord.com/channels/{}/{channel_id}/{message_id}",
+                    state.context.guild.id
+                ),
+                true,
+            );
+    }
+
+    if let Some(last_offense_date) = user.last_offense_date {
+        embed = embed.field(
+            "Last Offense",
+            format!("<t:{}:R>", last_offense_date.and_utc().timestamp()),
+            true,
+        );
+    }
+
+    if let (UserStatus::Pending, Some(claimed_by)) = (user.status, user.claimed_by) {
+        embed = embed.field("Claimed By", format!("<@{claimed_by}>"), true);
+    }
+
+    if let Some(decided_by) = user.decided_by {
+        let decision_date = user.decision_date.map_or_else(String::new, |date| {
+            format!(" <t:{}:R>", date.and_utc().timestamp())
+        });
+        embed = embed.field(
+            "Decided By",
+            format!("<@{decided_by}>{decision_date}"),
+            true,
+        );
+    }
+    if let Some(reason) = &user.decision_reason {
+        embed = embed.field("Reason", questionnaire::truncate(reason, 1024), false);
+    }
+
+    if let Some(application) = application {
+        embed = embed.field(
+            "Application",
+            questionnaire::truncate(
+                &format!(
+                    "**Age:** {}\n**Found through:** {}\n**Reason:** {}",
+                    application.age, application.source, application.reason
+                ),
+                1024,
+            ),
+            false,
+        );
+    }
+
+    if !interviews.is_empty() {
+        //* Only the latest interviews, so the field stays within the embed limits.
+        let mut lines = Vec::new();
+        let mut length = 0;
+        for interview in interviews.iter().rev().take(10) {
+            let tier = state
+                .context
+                .tier(&interview.tier)
+                .map_or(interview.tier.as_str(), |tier| tier.label.as_str());
+            let line = format!(
+                "`#{}` {tier} by <@{}> <t:{}:R>, {}",
+                interview.id,
+                interview.interviewer_id,
+                interview.start_date.and_utc().timestamp(),
+                outcome_name(interview.outcome)
+            );
+            //* Lines are joined by a line break each.
+            if length + line.chars().count() + lines.len() > 1024 {
+                break;
+            }
+            length += line.chars().count();
+            lines.push(line);
+        }
+        lines.reverse();
+        embed = embed.field("Interviews", lines.join("\n"), false);
+    }
+
+    if !answers.is_empty() {
+        //* Short answers only, the full ones are summarised in the interview channel.
+        let mut summary = String::new();
+        for answer in answers {
+            let line = format!(
+                "**{}.** {}\n",
+                answer.position + 1,
+                questionnaire::truncate(&answer.answer, 100)
+            );
+            if summary.len() + line.len() > 1024 {
+                break;
+            }
+            summary.push_str(&line);
+        }
+        embed = embed.field("Answers", summary, false);
+    }
+
+    embed
+}
+
+/// Posts the mark message of a user, or edits it to reflect their current record if it
+/// was already posted.
+pub async fn sync(http: &Http, state: &BouncerState, user_id: UserId) -> anyhow::Result<()> {
+    let database_user_id = database::to_id(user_id.get());
+    let Some(user) = User::fetch(&state.database, database_user_id).await? else {
+        debug!("user `{user_id}` has no records, not syncing their mark message");
+        return Ok(());
+    };
+
+    let application = Application::latest(&state.database, database_user_id).await?;
+    let interviews = Interview::fetch_all(&state.database, database_user_id).await?;
+    let answers = match interviews.last() {
+        Some(interview) if interview.outcome.is_none() => {
+            InterviewAnswer::fetch_all(&state.database, interview.id).await?
+        }
+        _ => vec![],
+    };
+    //* Approved users can be upgraded to tiers ranked above the one they're approved for.
+    let approved_rank = interviews
+        .iter()
+        .rev()
+        .find(|interview| interview.outcome == Some(InterviewOutcome::Approved))
+        .and_then(|interview| state.context.tier(&interview.tier))
+        .map(|tier| tier.rank);
+
+    let marks_channel_id = state.context.channels.interview_marks.id;
+    let mark_embed = || embed(state, &user, application.as_ref(), &interviews, &answers);
+    let buttons =
+        || components::mark::buttons(&state.context.tiers, user_id, user.status, approved_rank);
+
+    if let Some(message_id) = user.mark_message_id {
+        match marks_channel_id
+            .edit_message(
+                http,
+                MessageId::new(database::from_id(message_id)),
+                EditMessage::new().embed(mark_embed()).components(buttons()),
+            )
+            .await
+        {
+            Ok(_) => return Ok(()),
+            Err(error) => {
+                warn!("could not edit the mark message of `{user_id}`, posting a new one... ({error})");
+            }
+        }
+    }
+
+    let message = marks_channel_id
+        .send_message(
+            http,
+            CreateMessage::new()
+                .embed(mark_embed())
+                .components(buttons()),
+        )
+        .await?;
+    User::set_mark_message_id(
+        &state.database,
+        database_user_id,
+        database::to_id(message.id.get()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Flags a previously rejected user to the marks channel again for posting in a watched
+/// channel.
+pub async fn flag(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    channel_id: ChannelId,
+    message_id: MessageId,
+) -> anyhow::Result<()> {
+    let database_user_id = database::to_id(user_id.get());
+    let reason = User::fetch(&state.database, database_user_id)
+        .await?
+        .and_then(|user| user.decision_reason);
+
+    let mut embed = CreateEmbed::new()
+        .title("Rejected User Posted Again")
+        .colour(Colour::RED)
+        .field(
+            "User",
+            format!("{} (`{user_id}`)", user_id.mention()),
+            false,
+        )
+        .field("Channel", channel_id.mention().to_string(), true)
+        .field(
+            "Message",
+            format!(
+                "https://discord.com/channels/{}/{channel_id}/{message_id}",
+                state.context.guild.id
+            ),
+            true,
+        )
+        .footer(CreateEmbedFooter::new(user_id.to_string()));
+    if let Some(reason) = reason {
+        embed = embed.field(
+            "Rejection Reason",
+            questionnaire::truncate(&reason, 1024),
+            false,
+        );
+    }
+
+    state
+        .context
+        .channels
+        .interview_marks
+        .id
+        .send_message(http, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
+
+/// Logs what a rejoining member got back to the marks channel.
+pub async fn log_rejoin(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    status: UserStatus,
+    action: &str,
+) -> anyhow::Result<()> {
+    let embed = CreateEmbed::new()
+        .title("Member Rejoined")
+        .colour(Colour::BLUE)
+        .field(
+            "User",
+            format!("{} (`{user_id}`)", user_id.mention()),
+            false,
+        )
+        .field("Status", status_name(status), true)
+        .field("Restoration", action.to_string(), true)
+        .footer(CreateEmbedFooter::new(user_id.to_string()));
+
+    state
+        .context
+        .channels
+        .interview_marks
+        .id
+        .send_message(http, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
+
+/// Logs a verified role granted by hand and what was done about it to the marks channel.
+pub async fn log_manual_grant(
+    http: &Http,
+    state: &BouncerState,
+    user_id: UserId,
+    role_id: RoleId,
+    granted_by: UserId,
+    action: &str,
+) -> anyhow::Result<()> {
+    let embed = CreateEmbed::new()
+        .title("Verification Role Granted Manually")
+        .colour(Colour::ORANGE)
+        .field(
+            "User",
+            format!("{} (`{user_id}`)", user_id.mention()),
+            false,
+        )
+        .field("Role", role_id.mention().to_string(), true)
+        .field("Granted By", granted_by.mention().to_string(), true)
+        .field("Action", action.to_string(), false)
+        .footer(CreateEmbedFooter::new(user_id.to_string()));
+
+    state
+        .context
+        .channels
+        .interview_marks
+        .id
+        .send_message(http, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
+
+/// Flags an image posted during an ID verification interview that closely matches images
+/// of other users to the marks channel, pinging the interviewer. Matches come with how many
+/// bits of their perceptual hashes differ, closest first.
+pub async fn log_reused_image(
+    http: &Http,
+    state: &BouncerState,
+    interview: &Interview,
+    message: &Message,
+    filename: &str,
+    matches: &[(&ImageHash, u32)],
+) -> anyhow::Result<()> {
+    let interviewer_id = UserId::new(database::from_id(interview.interviewer_id));
+    let matched = matches
+        .iter()
+        .take(10)
+        .map(|(image_hash, distance)| {
+            format!(
+                "- <@{}> (`{}`) in interview #{}, {distance} bits apart",
+                image_hash.user_id, image_hash.user_id, image_hash.interview_id
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let embed = CreateEmbed::new()
+        .title("Possibly Reused ID Image")
+        .colour(Colour::RED)
+        .field(
+            "User",
+            format!("{} (`{}`)", message.author.id.mention(), message.author.id),
+            false,
+        )
+        .field("Interview", format!("#{}", interview.id), true)
+        .field("Image", filename.to_string(), true)
+        .field(
+            "Message",
+            format!(
+                "https://discord.com/channels/{}/{}/{}",
+                state.context.guild.id, message.channel_id, message.id
+            ),
+            true,
+        )
+        .field("Matches", matched, false)
+        .footer(CreateEmbedFooter::new(message.author.id.to_string()));
+
+    state
+        .context
+        .channels
+        .interview_marks
+        .id
+        .send_message(
+            http,
+            CreateMessage::new()
+                .content(format!(
+                    "{}, an image posted in your interview looks like one posted by another user.",
+                    interviewer_id.mention()
+                ))
+                .embed(embed),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Syncs the mark message of a user, logging instead of failing, since mark messages are
+/// informational and shouldn't interrupt the action that triggered them.
+pub async fn sync_or_warn(http: &Http, state: &BouncerState, user_id: UserId) {
+    if let Err(error) = sync(http, state, user_id).await {
+        warn!("could not sync the mark message of `{user_id}`: {error}");
+    }
+}
@@ -2,17 +2,34 @@ use std::sync::Arc;
 
 use serenity::{all::GatewayIntents, Client};
 use sqlx::SqlitePool;
-use tokio::sync::RwLock;
-use tracing::trace;
+use tokio::{sync::RwLock, time::Duration};
+use tracing::{info, trace};
 
 use crate::config;
 
+pub use audit::export as export_audit_log;
+pub use transcript::{export as export_transcript, Format as TranscriptFormat};
+
+mod audit;
+mod backfill;
 mod commands;
+mod components;
 mod context;
 mod database;
 mod event_handler;
 mod extensions;
 mod helpers;
+mod id_verification;
+mod jobs;
+mod lifecycle;
+mod manual_grants;
+mod marking;
+mod marks;
+mod questionnaire;
+mod reconcile;
+mod rejoin;
+mod status;
+mod transcript;
 
 pub struct BouncerBot {
     token: String,
@@ -36,21 +53,102 @@ impl BouncerBot {
     }
 
     pub async fn start(&self, discord_config: config::Discord) -> anyhow::Result<()> {
+        migrate_stored_tiers(&self.state.read().await.database, &discord_config.tiers).await?;
+
+        let stale_interviews_job = jobs::stale_interviews::Job {
+            stale_after_minutes: discord_config.interviews.stale_after_minutes,
+            check_interval: Duration::from_secs(
+                discord_config.interviews.stale_check_interval_minutes.get() * 60,
+            ),
+        };
+        let attachment_purges_job = jobs::attachment_purges::Job {
+            check_interval: Duration::from_secs(
+                discord_config.interviews.purge_retry_interval_minutes.get() * 60,
+            ),
+        };
+        let reconcile_job = discord_config
+            .reconcile
+            .interval_minutes
+            .map(|interval_minutes| jobs::reconcile::Job {
+                check_interval: Duration::from_secs(interval_minutes.get() * 60),
+            });
+
         trace!("creating the Discord client...");
+        //* Message contents are needed to record the answers of interviewees.
         let mut client = Client::builder(
             &self.token,
-            GatewayIntents::GUILDS | GatewayIntents::GUILD_MESSAGES | GatewayIntents::GUILD_MEMBERS,
+            GatewayIntents::GUILDS
+                | GatewayIntents::GUILD_MESSAGES
+                | GatewayIntents::GUILD_MEMBERS
+                | GatewayIntents::MESSAGE_CONTENT,
         )
         .event_handler(event_handler::BouncerEventHandler {
-            discord_config,
+            discord_config: Arc::new(discord_config),
             state: self.state.clone(),
+            backfill_until: Arc::default(),
         })
         .await?;
         trace!("created the Discord client");
 
+        trace!("spawning the background jobs...");
+        jobs::spawn(
+            stale_interviews_job,
+            client.http.clone(),
+            self.state.clone(),
+        );
+        jobs::spawn(
+            attachment_purges_job,
+            client.http.clone(),
+            self.state.clone(),
+        );
+        if let Some(reconcile_job) = reconcile_job {
+            jobs::spawn(reconcile_job, client.http.clone(), self.state.clone());
+        }
+        trace!("spawned the background jobs");
+
         trace!("starting the Discord bot...");
         client.start().await?;
 
         Ok(())
     }
 }
+
+/// Renames interviews stored under tier aliases to their tier, then makes sure every stored
+/// tier is configured, since interviews of unknown tiers can't be approved or reconciled.
+async fn migrate_stored_tiers(
+    database: &SqlitePool,
+    tiers: &[config::DiscordTier],
+) -> anyhow::Result<()> {
+    for tier in tiers {
+        for alias in &tier.aliases {
+            if tiers.iter().any(|other| &other.name == alias) {
+                anyhow::bail!(
+                    "alias `{alias}` of the `{}` tier is the name of a tier",
+                    tier.name
+                );
+            }
+
+            let renamed = database::Interview::rename_tier(database, alias, &tier.name).await?;
+            if renamed > 0 {
+                info!(
+                    "renamed {renamed} interviews of the `{alias}` tier to `{}`",
+                    tier.name
+                );
+            }
+        }
+    }
+
+    let unknown_tiers: Vec<String> = database::Interview::fetch_tier_names(database)
+        .await?
+        .into_iter()
+        .filter(|name| !tiers.iter().any(|tier| &tier.name == name))
+        .collect();
+    if !unknown_tiers.is_empty() {
+        anyhow::bail!(
+            "interviews are stored with tiers that aren't configured: `{}`, add them to `tiers` or to the `aliases` of a tier",
+            unknown_tiers.join("`, `")
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,189 @@
+//! Questions of a tier asked one at a time in the interview channel, with the answers of the
+//! interviewee recorded against their interview.
+
+use serenity::all::{
+    ChannelId, Colour, CreateEmbed, CreateEmbedFooter, CreateMessage, Http, Mentionable, Message,
+    UserId,
+};
+use tracing::{debug, warn};
+
+use super::{
+    context::Tier,
+    database::{self, Interview, InterviewAnswer},
+    marks, BouncerState,
+};
+
+/// Shortens a text to at most `max_characters`, marking where it was cut.
+pub fn truncate(text: &str, max_characters: usize) -> String {
+    if text.chars().count() <= max_characters {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text
+        .chars()
+        .take(max_characters.saturating_sub(1))
+        .collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Asks the first unanswered question of the tier in the interview channel, or posts a
+/// summary of the answers for the interviewer once every question is answered.
+pub async fn ask_next(
+    http: &Http,
+    state: &BouncerState,
+    interview_id: i64,
+    user_id: UserId,
+    channel_id: ChannelId,
+    tier: &Tier,
+) -> anyhow::Result<()> {
+    if tier.questions.is_empty() {
+        return Ok(());
+    }
+
+    let answered = usize::try_from(InterviewAnswer::count(&state.database, interview_id).await?)?;
+    let message = match tier.questions.get(answered) {
+        Some(question) => {
+            let mut content = String::new();
+            if answered == 0 {
+                content.push_str(&format!(
+                    "{}, please answer the following {} questions, one message per answer.\n\n",
+                    user_id.mention(),
+                    tier.questions.len()
+                ));
+            }
+            content.push_str(&format!(
+                "**Question {} of {}**\n{question}",
+                answered + 1,
+                tier.questions.len()
+            ));
+
+            CreateMessage::new().content(content)
+        }
+        None => {
+            let answers = InterviewAnswer::fetch_all(&state.database, interview_id).await?;
+            CreateMessage::new()
+                .content("Thank you! An interviewer will go through your answers shortly.")
+                .embed(summary(user_id, tier, &answers))
+        }
+    };
+    channel_id.send_message(http, message).await?;
+
+    Ok(())
+}
+
+/// Asks the next question, logging instead of failing, since the interview goes on without
+/// the questionnaire.
+pub async fn ask_next_or_warn(
+    http: &Http,
+    state: &BouncerState,
+    interview_id: i64,
+    user_id: UserId,
+    channel_id: ChannelId,
+    tier: &Tier,
+) {
+    if let Err(error) = ask_next(http, state, interview_id, user_id, channel_id, tier).await {
+        warn!("could not ask the next question of the interview `{interview_id}`: {error}");
+    }
+}
+
+/// Records a message as the answer to the current question if it was posted by an
+/// interviewee in their interview channel, then asks the next question. Returns whether the
+/// message was recorded as an answer.
+pub async fn record_answer(
+    http: &Http,
+    state: &BouncerState,
+    message: &Message,
+) -> anyhow::Result<bool> {
+    let Some(interview) = Interview::fetch_ongoing_by_channel(
+        &state.database,
+        database::to_id(message.channel_id.get()),
+    )
+    .await?
+    else {
+        return Ok(false);
+    };
+    if interview.user_id != database::to_id(message.author.id.get()) {
+        return Ok(false);
+    }
+    let Some(tier) = state.context.tier(&interview.tier) else {
+        return Ok(false);
+    };
+
+    let answered = InterviewAnswer::count(&state.database, interview.id).await?;
+    let Some(question) = tier.questions.get(usize::try_from(answered)?) else {
+        return Ok(false);
+    };
+
+    //* Only the names of attachments are kept, the files stay on Discord.
+    let mut answer = message.content.trim().to_string();
+    for attachment in &message.attachments {
+        if !answer.is_empty() {
+            answer.push('\n');
+        }
+        answer.push_str(&format!("[attachment: {}]", attachment.filename));
+    }
+    if answer.is_empty() {
+        return Ok(false);
+    }
+
+    if !InterviewAnswer::insert(&state.database, interview.id, answered, question, &answer).await? {
+        debug!(
+            "question `{answered}` of the interview `{}` was answered already, ignoring...",
+            interview.id
+        );
+        return Ok(false);
+    }
+
+    ask_next_or_warn(
+        http,
+        state,
+        interview.id,
+        message.author.id,
+        message.channel_id,
+        tier,
+    )
+    .await;
+    marks::sync_or_warn(http, state, message.author.id).await;
+
+    Ok(true)
+}
+
+/// Discord rejects embeds with more characters than this across all of their texts.
+const EMBED_CHARACTER_LIMIT: usize = 6000;
+
+/// Builds an embed listing every question of an interview along with its answer, as many as
+/// fit into an embed.
+pub fn summary<'a>(user_id: UserId, tier: &Tier, answers: &[InterviewAnswer]) -> CreateEmbed<'a> {
+    let title = format!("{} Interview Answers", tier.label);
+    let footer = user_id.to_string();
+
+    //* Room is kept for the description telling how many answers were left out.
+    let mut total_characters = title.chars().count() + footer.chars().count() + 100;
+    let mut fields = Vec::with_capacity(answers.len());
+    for answer in answers {
+        let question = truncate(&answer.question, 256);
+        let answer = truncate(&answer.answer, 1024);
+        let characters = question.chars().count() + answer.chars().count();
+        if total_characters + characters > EMBED_CHARACTER_LIMIT {
+            break;
+        }
+
+        total_characters += characters;
+        fields.push((question, answer, false));
+    }
+
+    let left_out = answers.len() - fields.len();
+    let mut embed = CreateEmbed::new()
+        .title(title)
+        .colour(Colour::BLUE)
+        .fields(fields)
+        .footer(CreateEmbedFooter::new(footer));
+    if left_out > 0 {
+        embed = embed.description(format!(
+            "{left_out} more answers didn't fit, see the transcript of the interview for them."
+        ));
+    }
+
+    embed
+}
@@ -0,0 +1,236 @@
+//! Reconciliation of member roles with the database records, since roles can be edited by
+//! hand.
+
+use std::collections::HashMap;
+
+use serenity::{
+    all::{Http, Member, RoleId, UserId},
+    futures::StreamExt,
+};
+use tracing::{info, warn};
+
+use super::{
+    database::{self, AuditAction, AuditEntry, Interview, User, UserStatus},
+    BouncerState,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    /// The member holds the role of a tier without being approved for it.
+    UnapprovedRole { user_id: UserId, role_id: RoleId },
+    /// The member is approved but is missing the role of their tier.
+    MissingRole { user_id: UserId, role_id: RoleId },
+    /// The member holds both the pending and the ongoing interview roles, the given role is
+    /// the one that doesn't match their status.
+    PendingAndOngoing {
+        user_id: UserId,
+        extra_role_id: RoleId,
+    },
+}
+
+impl Drift {
+    /// Whether fixing the drift takes a role away from the member.
+    pub const fn removes_role(&self) -> bool {
+        !matches!(self, Self::MissingRole { .. })
+    }
+}
+
+impl std::fmt::Display for Drift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnapprovedRole { user_id, role_id } => write!(
+                f,
+                "<@{user_id}> holds <@&{role_id}> without an approved interview"
+            ),
+            Self::MissingRole { user_id, role_id } => {
+                write!(f, "<@{user_id}> is approved but is missing <@&{role_id}>")
+            }
+            Self::PendingAndOngoing {
+                user_id,
+                extra_role_id,
+            } => write!(
+                f,
+                "<@{user_id}> holds both interview roles, <@&{extra_role_id}> doesn't match their status"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub checked_members: usize,
+    pub drifts: Vec<Drift>,
+    pub fixed: usize,
+    /// Role removals left out since they went over the limit.
+    pub over_limit: usize,
+}
+
+impl Report {
+    /// Summarises the report in a message that fits into a Discord message.
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Checked {} members, found {} inconsistencies",
+            self.checked_members,
+            self.drifts.len()
+        );
+        if self.fixed > 0 {
+            summary.push_str(&format!(" and fixed {}", self.fixed));
+        }
+        summary.push('.');
+        if self.over_limit > 0 {
+            summary.push_str(&format!(
+                " {} role removals went over the limit and were left out.",
+                self.over_limit
+            ));
+        }
+
+        for (index, drift) in self.drifts.iter().enumerate() {
+            let line = format!("\n- {drift}");
+            if summary.len() + line.len() > 1900 {
+                summary.push_str(&format!("\n... and {} more.", self.drifts.len() - index));
+                break;
+            }
+            summary.push_str(&line);
+        }
+
+        summary
+    }
+}
+
+/// Walks every guild member and compares their roles with their record, fixing the drift
+/// if given how many roles can be removed at most.
+///
+/// The drift of members without a record is only reported, since there is nothing to tell
+/// their roles apart from the ones given by hand before the bot kept track.
+pub async fn run(
+    http: &Http,
+    state: &BouncerState,
+    removal_limit: Option<usize>,
+) -> anyhow::Result<Report> {
+    let statuses: HashMap<i64, UserStatus> = User::fetch_statuses(&state.database)
+        .await?
+        .into_iter()
+        .collect();
+    let approved_tiers: HashMap<i64, String> = Interview::fetch_approved_tiers(&state.database)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut report = Report::default();
+    let mut removals = 0;
+    let mut members = state.context.guild.id.members_iter(http).boxed();
+    while let Some(member) = members.next().await {
+        let member = member?;
+        if member.user.bot() {
+            continue;
+        }
+
+        let database_user_id = database::to_id(member.user.id.get());
+        let status = statuses.get(&database_user_id).copied();
+        report.checked_members += 1;
+
+        let approved_tier = approved_tiers.get(&database_user_id).map(String::as_str);
+        for drift in check_member(state, &member, status, approved_tier) {
+            //* Members without a record may have been verified before the bot kept track.
+            if let Some(removal_limit) = removal_limit.filter(|_| status.is_some()) {
+                if drift.removes_role() {
+                    if removals >= removal_limit {
+                        report.over_limit += 1;
+                        report.drifts.push(drift);
+                        continue;
+                    }
+                    removals += 1;
+                }
+
+                match fix_drift(http, state, &drift).await {
+                    Ok(()) => {
+                        report.fixed += 1;
+                        AuditEntry::record(
+                            &state.database,
+                            AuditAction::RoleChanged,
+                            None,
+                            database_user_id,
+                            None,
+                            Some(&format!("Fixed: {drift}.")),
+                        )
+                        .await?;
+                    }
+                    Err(error) => warn!("could not fix the drift `{drift:?}`: {error}"),
+                }
+            }
+            report.drifts.push(drift);
+        }
+    }
+
+    info!(
+        "reconciled {} members, found {} inconsistencies and fixed {}",
+        report.checked_members,
+        report.drifts.len(),
+        report.fixed
+    );
+
+    Ok(report)
+}
+
+fn check_member(
+    state: &BouncerState,
+    member: &Member,
+    status: Option<UserStatus>,
+    approved_tier: Option<&str>,
+) -> Vec<Drift> {
+    let roles = &state.context.roles;
+    let user_id = member.user.id;
+    let mut drifts = Vec::new();
+
+    //* Users being upgraded keep the role of their previous tier until the interview ends.
+    let approved_role_id = match (status, approved_tier) {
+        (Some(UserStatus::Approved | UserStatus::Ongoing), Some(tier)) => {
+            state.context.tier(tier).map(|tier| tier.role.id)
+        }
+        _ => None,
+    };
+
+    for role_id in state.context.tiers.iter().map(|tier| tier.role.id) {
+        let holds_role = member.roles.contains(&role_id);
+        if holds_role && approved_role_id != Some(role_id) {
+            drifts.push(Drift::UnapprovedRole { user_id, role_id });
+        } else if !holds_role && approved_role_id == Some(role_id) {
+            drifts.push(Drift::MissingRole { user_id, role_id });
+        }
+    }
+
+    if member.roles.contains(&roles.pending_interview.id)
+        && member.roles.contains(&roles.ongoing_interview.id)
+    {
+        let extra_role_id = match status {
+            Some(UserStatus::Ongoing) => roles.pending_interview.id,
+            _ => roles.ongoing_interview.id,
+        };
+        drifts.push(Drift::PendingAndOngoing {
+            user_id,
+            extra_role_id,
+        });
+    }
+
+    drifts
+}
+
+async fn fix_drift(http: &Http, state: &BouncerState, drift: &Drift) -> serenity::Result<()> {
+    let guild_id = state.context.guild.id;
+    let reason = Some("Reconciled with the interview records.");
+
+    match *drift {
+        Drift::UnapprovedRole { user_id, role_id }
+        | Drift::PendingAndOngoing {
+            user_id,
+            extra_role_id: role_id,
+        } => {
+            http.remove_member_role(guild_id, user_id, role_id, reason)
+                .await
+        }
+        Drift::MissingRole { user_id, role_id } => {
+            http.add_member_role(guild_id, user_id, role_id, reason)
+                .await
+        }
+    }
+}
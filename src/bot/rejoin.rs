@@ -0,0 +1,84 @@
+//! Restoring the outcome of previous interviews when a member rejoins.
+
+use serenity::all::{Http, Member, RoleId};
+
+use super::{
+    database::{AuditAction, AuditEntry, Interview, User, UserStatus},
+    marks, BouncerState,
+};
+
+use crate::config;
+
+/// What a rejoining member gets, based on their records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Restoration {
+    Role(RoleId),
+    Kick,
+    Nothing,
+}
+
+/// Decides what a rejoining member gets from their status and latest approved interview,
+/// nothing if the tier of the interview is not configured anymore.
+pub async fn plan(
+    state: &BouncerState,
+    discord_config: &config::Discord,
+    user: &User,
+) -> sqlx::Result<Restoration> {
+    let roles = &state.context.roles;
+
+    Ok(match user.status {
+        UserStatus::Approved => Interview::latest_approved(&state.database, user.user_id)
+            .await?
+            .and_then(|interview| state.context.tier(&interview.tier))
+            .map_or(Restoration::Nothing, |tier| Restoration::Role(tier.role.id)),
+        UserStatus::Pending => Restoration::Role(roles.pending_interview.id),
+        UserStatus::Ongoing => Restoration::Role(roles.ongoing_interview.id),
+        UserStatus::Rejected => match discord_config.interviews.rejected_rejoin {
+            config::RejectedRejoin::Nothing => Restoration::Nothing,
+            config::RejectedRejoin::Role => roles
+                .rejected
+                .as_ref()
+                .map_or(Restoration::Nothing, |role| Restoration::Role(role.id)),
+            config::RejectedRejoin::Kick => Restoration::Kick,
+        },
+    })
+}
+
+/// Restores the outcome of previous interviews of a rejoining member and logs it to the
+/// marks channel.
+pub async fn restore(
+    http: &Http,
+    state: &BouncerState,
+    discord_config: &config::Discord,
+    member: &Member,
+    user: &User,
+) -> anyhow::Result<Restoration> {
+    let restoration = plan(state, discord_config, user).await?;
+
+    let action = match &restoration {
+        Restoration::Role(role_id) => {
+            member
+                .add_role(http, *role_id, Some("Restored on rejoin."))
+                .await?;
+            format!("Gave back <@&{role_id}>.")
+        }
+        Restoration::Kick => {
+            member.kick(http, Some("Rejected user rejoined.")).await?;
+            "Kicked, since they were rejected.".to_string()
+        }
+        Restoration::Nothing => "Nothing to give back.".to_string(),
+    };
+
+    AuditEntry::record(
+        &state.database,
+        AuditAction::Restored,
+        None,
+        user.user_id,
+        None,
+        Some(&action),
+    )
+    .await?;
+    marks::log_rejoin(http, state, member.user.id, user.status, &action).await?;
+
+    Ok(restoration)
+}
@@ -0,0 +1,171 @@
+//! The statuses a user goes through and the transitions allowed between them.
+//!
+//! Statuses are only changed through a [`Transition`], which can only be created by
+//! [`transition`], so invalid changes are caught before they reach the database.
+
+use super::database::UserStatus;
+
+impl UserStatus {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Ongoing => "ongoing",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+impl std::fmt::Display for UserStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A change of status that is not allowed. Users without records have no status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidTransition {
+    pub from: Option<UserStatus>,
+    pub to: UserStatus,
+}
+
+impl std::fmt::Display for InvalidTransition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.from {
+            Some(from) => write!(f, "A user cannot go from `{from}` to `{}`.", self.to),
+            None => write!(f, "A user without records cannot become `{}`.", self.to),
+        }
+    }
+}
+
+impl std::error::Error for InvalidTransition {}
+
+/// A validated change of status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    from: Option<UserStatus>,
+    to: UserStatus,
+}
+
+impl Transition {
+    pub const fn from(self) -> Option<UserStatus> {
+        self.from
+    }
+
+    pub const fn to(self) -> UserStatus {
+        self.to
+    }
+}
+
+/// Whether a user can go from one status to another.
+pub const fn is_allowed(from: Option<UserStatus>, to: UserStatus) -> bool {
+    use UserStatus::{Approved, Ongoing, Pending, Rejected};
+
+    matches!(
+        (from, to),
+        // Marked, or interviewed and approved right away.
+        (None, Pending | Ongoing | Approved)
+            // Interviewed, rejected without an interview or approved by hand.
+            | (Some(Pending), Ongoing | Rejected | Approved)
+            // Decided, or reverted back when the interview goes stale.
+            | (Some(Ongoing), Approved | Rejected | Pending)
+            // Interviewed again to upgrade their tier, or approved for another tier by hand.
+            | (Some(Approved), Ongoing | Approved)
+            // Appealed, interviewed again or approved by hand.
+            | (Some(Rejected), Pending | Ongoing | Approved)
+    )
+}
+
+/// Marking a user without records as pending, validated when compiling.
+pub const MARK: Transition = match transition(None, UserStatus::Pending) {
+    Ok(transition) => transition,
+    Err(_) => panic!("users without records can't be marked"),
+};
+
+/// Validates a change of status.
+pub const fn transition(
+    from: Option<UserStatus>,
+    to: UserStatus,
+) -> Result<Transition, InvalidTransition> {
+    if is_allowed(from, to) {
+        Ok(Transition { from, to })
+    } else {
+        Err(InvalidTransition { from, to })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_allowed, transition, InvalidTransition, Transition, MARK};
+    use crate::bot::database::UserStatus::{self, Approved, Ongoing, Pending, Rejected};
+
+    const STATUSES: [UserStatus; 4] = [Pending, Ongoing, Approved, Rejected];
+
+    #[test]
+    fn allows_the_interview_flow() {
+        assert!(is_allowed(None, Pending));
+        assert!(is_allowed(Some(Pending), Ongoing));
+        assert!(is_allowed(Some(Ongoing), Approved));
+        assert!(is_allowed(Some(Ongoing), Rejected));
+    }
+
+    #[test]
+    fn allows_reverting_stale_interviews() {
+        assert!(is_allowed(Some(Ongoing), Pending));
+    }
+
+    #[test]
+    fn allows_appeals_and_upgrades() {
+        assert!(is_allowed(Some(Rejected), Pending));
+        assert!(is_allowed(Some(Approved), Ongoing));
+    }
+
+    #[test]
+    fn rejects_repeating_statuses_besides_approvals() {
+        for status in [Pending, Ongoing, Rejected] {
+            assert!(!is_allowed(Some(status), status), "{status} to {status}");
+        }
+        assert!(is_allowed(Some(Approved), Approved));
+    }
+
+    #[test]
+    fn rejects_undoing_approvals() {
+        assert!(!is_allowed(Some(Approved), Pending));
+        assert!(!is_allowed(Some(Approved), Rejected));
+    }
+
+    #[test]
+    fn rejects_rejecting_users_without_records() {
+        assert!(!is_allowed(None, Rejected));
+    }
+
+    #[test]
+    fn transitions_match_the_table() {
+        #[rustfmt::skip]
+        let table = [
+            //             Pending Ongoing Approved Rejected
+            (None,          [true,  true,   true,    false]),
+            (Some(Pending), [false, true,   true,    true]),
+            (Some(Ongoing), [true,  false,  true,    true]),
+            (Some(Approved),[false, true,   true,    false]),
+            (Some(Rejected),[true,  true,   true,    false]),
+        ];
+
+        for (from, allowed) in table {
+            for (to, allowed) in STATUSES.into_iter().zip(allowed) {
+                let expected = if allowed {
+                    Ok(Transition { from, to })
+                } else {
+                    Err(InvalidTransition { from, to })
+                };
+                assert_eq!(transition(from, to), expected, "{from:?} to {to}");
+            }
+        }
+    }
+
+    #[test]
+    fn marks_users_without_records() {
+        assert_eq!(MARK.from(), None);
+        assert_eq!(MARK.to(), Pending);
+    }
+}
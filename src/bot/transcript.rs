@@ -0,0 +1,296 @@
+//! Transcripts of interview channels, captured when interviews end so they can be reviewed
+//! on appeals after the channels are gone.
+
+use chrono::{DateTime, NaiveDateTime};
+use serenity::all::{ChannelId, GetMessages, Http, Message, MessageId};
+use sqlx::SqlitePool;
+use tracing::{info, warn};
+
+use super::{
+    database::{self, Interview, TranscriptMessage},
+    marks, BouncerState,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+impl Format {
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// Fetches every message of a channel, oldest first.
+pub async fn fetch_messages(http: &Http, channel_id: ChannelId) -> serenity::Result<Vec<Message>> {
+    //* Snowflakes are ordered by time, so every message of a channel comes after its ID.
+    let mut after = MessageId::new(channel_id.get());
+    let mut all_messages = Vec::new();
+    loop {
+        let mut messages = channel_id
+            .messages(http, GetMessages::new().after(after).limit(100))
+            .await?;
+        if messages.is_empty() {
+            break;
+        }
+        messages.sort_by_key(|message| message.id);
+
+        after = messages[messages.len() - 1].id;
+        all_messages.extend(messages);
+    }
+
+    Ok(all_messages)
+}
+
+/// The time a message was posted at.
+pub fn message_date(message: &Message) -> NaiveDateTime {
+    DateTime::from_timestamp(message.timestamp.unix_timestamp(), 0)
+        .map_or_else(NaiveDateTime::default, |date| date.naive_utc())
+}
+
+/// Records every message of the channel of an interview. Returns how many were captured.
+pub async fn capture(
+    http: &Http,
+    state: &BouncerState,
+    interview: &Interview,
+) -> anyhow::Result<usize> {
+    let Some(channel_id) = interview.channel_id else {
+        return Ok(0);
+    };
+    let messages = fetch_messages(http, ChannelId::new(database::from_id(channel_id))).await?;
+
+    for message in &messages {
+        let attachments = message
+            .attachments
+            .iter()
+            .map(|attachment| attachment.filename.to_string())
+            .collect::<Vec<_>>();
+        TranscriptMessage::upsert(
+            &state.database,
+            &TranscriptMessage {
+                interview_id: interview.id,
+                message_id: database::to_id(message.id.get()),
+                author_id: database::to_id(message.author.id.get()),
+                author_name: message.author.name.to_string(),
+                content: message.content.to_string(),
+                attachments: (!attachments.is_empty()).then(|| attachments.join("\n")),
+                message_date: message_date(message),
+            },
+        )
+        .await?;
+    }
+
+    info!(
+        "captured {} messages of the interview `{}`",
+        messages.len(),
+        interview.id
+    );
+
+    Ok(messages.len())
+}
+
+/// Captures the transcript of an interview, logging instead of failing, since the interview
+/// ends either way.
+pub async fn capture_or_warn(http: &Http, state: &BouncerState, interview: &Interview) {
+    if let Err(error) = capture(http, state, interview).await {
+        warn!(
+            "could not capture the transcript of the interview `{}`: {error}",
+            interview.id
+        );
+    }
+}
+
+/// Renders the captured transcript of an interview.
+pub async fn render(
+    database: &SqlitePool,
+    interview: &Interview,
+    format: Format,
+) -> sqlx::Result<String> {
+    let messages = TranscriptMessage::fetch_all(database, interview.id).await?;
+
+    Ok(match format {
+        Format::Markdown => render_markdown(interview, &messages),
+        Format::Html => render_html(interview, &messages),
+    })
+}
+
+/// Renders the transcript of the interview with the given ID, none if it doesn't exist.
+pub async fn export(
+    database: &SqlitePool,
+    interview_id: i64,
+    format: Format,
+) -> anyhow::Result<Option<String>> {
+    let Some(interview) = Interview::fetch(database, interview_id).await? else {
+        return Ok(None);
+    };
+
+    Ok(Some(render(database, &interview, format).await?))
+}
+
+fn format_date(date: NaiveDateTime) -> String {
+    date.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+}
+
+/// The details of an interview shown above its messages.
+fn details(interview: &Interview) -> Vec<(&'static str, String)> {
+    let mut details = vec![
+        ("User", interview.user_id.to_string()),
+        ("Interviewer", interview.interviewer_id.to_string()),
+        ("Tier", interview.tier.clone()),
+        ("Started", format_date(interview.start_date)),
+    ];
+    if let Some(end_date) = interview.end_date {
+        details.push(("Ended", format_date(end_date)));
+    }
+    details.push((
+        "Outcome",
+        marks::outcome_name(interview.outcome).to_string(),
+    ));
+    if let Some(reason) = &interview.reason {
+        details.push(("Reason", reason.clone()));
+    }
+
+    details
+}
+
+fn render_markdown(interview: &Interview, messages: &[TranscriptMessage]) -> String {
+    let mut markdown = format!("# Transcript of Interview {}\n\n", interview.id);
+    for (name, value) in details(interview) {
+        markdown.push_str(&format!("- **{name}:** {}\n", escape_markdown(&value)));
+    }
+
+    markdown.push_str("\n## Messages\n");
+    if messages.is_empty() {
+        markdown.push_str("\nNo messages were captured.\n");
+    }
+    for message in messages {
+        markdown.push_str(&format!(
+            "\n**{}** (`{}`) at {}\n",
+            escape_markdown(&message.author_name),
+            message.author_id,
+            format_date(message.message_date)
+        ));
+        for line in message.content.lines() {
+            markdown.push_str(&format!("> {}\n", escape_markdown(line)));
+        }
+        if let Some(attachments) = &message.attachments {
+            markdown.push_str(&format!(
+                "\nAttachments: {}\n",
+                escape_markdown(&attachments.lines().collect::<Vec<_>>().join(", "))
+            ));
+        }
+    }
+
+    markdown
+}
+
+/// Escapes the characters Markdown could read as formatting, links or HTML, so texts of
+/// users show up as they were written.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        if matches!(
+            character,
+            '\\' | '`' | '*' | '_' | '~' | '|' | '<' | '>' | '#' | '[' | ']' | '(' | ')' | '!'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+
+    escaped
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+fn render_html(interview: &Interview, messages: &[TranscriptMessage]) -> String {
+    let title = format!("Transcript of Interview {}", interview.id);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n\
+         body {{ font-family: sans-serif; max-width: 50rem; margin: 2rem auto; }}\n\
+         .message {{ border-top: 1px solid #ccc; padding: 0.5rem 0; }}\n\
+         .author {{ font-weight: bold; }}\n\
+         .date, .attachments {{ color: #666; font-size: 0.9rem; }}\n\
+         .content {{ white-space: pre-wrap; }}\n\
+         </style>\n</head>\n<body>\n<h1>{title}</h1>\n<dl>\n"
+    );
+    for (name, value) in details(interview) {
+        html.push_str(&format!(
+            "<dt>{name}</dt><dd>{}</dd>\n",
+            escape_html(&value)
+        ));
+    }
+    html.push_str("</dl>\n<h2>Messages</h2>\n");
+
+    if messages.is_empty() {
+        html.push_str("<p>No messages were captured.</p>\n");
+    }
+    for message in messages {
+        html.push_str(&format!(
+            "<div class=\"message\">\n<span class=\"author\">{}</span> \
+             <span class=\"date\">({}) at {}</span>\n<div class=\"content\">{}</div>\n",
+            escape_html(&message.author_name),
+            message.author_id,
+            format_date(message.message_date),
+            escape_html(&message.content)
+        ));
+        if let Some(attachments) = &message.attachments {
+            html.push_str(&format!(
+                "<div class=\"attachments\">Attachments: {}</div>\n",
+                escape_html(&attachments.lines().collect::<Vec<_>>().join(", "))
+            ));
+        }
+        html.push_str("</div>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_html, escape_markdown};
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(
+            escape_html("<script>alert(\"hi\" & 'bye')</script>"),
+            "&lt;script&gt;alert(&quot;hi&quot; &amp; &#39;bye&#39;)&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn escapes_markdown() {
+        assert_eq!(
+            escape_markdown("**bold** [link](https://example.com) <b>`code`</b>"),
+            "\\*\\*bold\\*\\* \\[link\\]\\(https://example.com\\) \\<b\\>\\`code\\`\\</b\\>"
+        );
+        assert_eq!(escape_markdown("# not_a_heading"), "\\# not\\_a\\_heading");
+    }
+
+    #[test]
+    fn leaves_plain_text_as_is() {
+        assert_eq!(escape_html("hello there"), "hello there");
+        assert_eq!(escape_markdown("hello there"), "hello there");
+    }
+}
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
 
 #[derive(Parser, Debug, Serialize, Deserialize)]
@@ -18,4 +18,33 @@ pub enum SubCommands {
         #[arg(env, short, long, default_value = "config.yaml")]
         config: PathBuf,
     },
+    /// Export the audit log as CSV
+    ExportAuditLog {
+        /// Path of configuration file
+        #[arg(env, short, long, default_value = "config.yaml")]
+        config: PathBuf,
+        /// Path of the CSV file to write, standard output if not specified
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Export the transcript of an interview
+    Transcript {
+        /// ID of the interview
+        interview_id: i64,
+        /// Path of configuration file
+        #[arg(env, short, long, default_value = "config.yaml")]
+        config: PathBuf,
+        /// Format of the transcript
+        #[arg(short, long, value_enum, default_value_t = TranscriptFormat::Markdown)]
+        format: TranscriptFormat,
+        /// Path of the file to write, standard output if not specified
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum TranscriptFormat {
+    Markdown,
+    Html,
 }
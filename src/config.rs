@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{num::NonZeroU64, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 
@@ -14,6 +14,14 @@ pub struct Config {
     pub discord: Discord,
 }
 
+/// The part of [`Config`] needed by subcommands that only work with the database.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DatabaseConfig {
+    /// The path of the `SQLite` database file.
+    #[serde(default = "default_database_path")]
+    pub database: PathBuf,
+}
+
 /// Default path for the `SQLite` database.
 fn default_database_path() -> PathBuf {
     PathBuf::from("data/db.sqlite")
@@ -35,12 +43,26 @@ pub struct Discord {
     pub channels: DiscordChannels,
     /// IDs for Discord roles.
     pub roles: DiscordRoles,
+    /// Verification tiers users can be interviewed for, at least one is required.
+    pub tiers: Vec<DiscordTier>,
+
+    /// Interview specific configurations.
+    #[serde(default)]
+    pub interviews: DiscordInterviews,
+    /// Rules for marking users to be interviewed.
+    #[serde(default)]
+    pub rules: DiscordRules,
+    /// Periodic reconciliation of roles with the database records.
+    #[serde(default)]
+    pub reconcile: DiscordReconcile,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DiscordChannels {
     /// Channel ID for user mark messages.
     pub interview_marks_id: u64,
+    /// Category ID where private interview channels are created.
+    pub interviews_category_id: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -53,8 +75,235 @@ pub struct DiscordRoles {
     /// Role ID for users currently being interviewed.
     pub ongoing_interview_id: u64,
 
-    /// Role ID for text-verified users.
-    pub text_verified_id: u64,
-    /// Role ID for ID-verified users.
-    pub id_verified_id: u64,
+    /// Role ID for rejected users, given back to them when they rejoin.
+    #[serde(default)]
+    pub rejected_id: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiscordTier {
+    /// Unique name of the tier stored with interviews, made of lowercase letters, digits,
+    /// `_` and `-`.
+    pub name: String,
+    /// Previous names of the tier, which interviews stored under them are renamed from at
+    /// startup. The `text` and `id` interview types from before tiers were configurable
+    /// carry over this way.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Name shown on buttons and command choices, at most 80 characters, defaults to the
+    /// name.
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Role ID for users verified for the tier.
+    pub role_id: u64,
+    /// Role IDs of which interviewers need at least one to interview for the tier, any
+    /// interviewer can if empty.
+    #[serde(default)]
+    pub interviewer_role_ids: Vec<u64>,
+    /// Position of the tier among the others, higher ranks supersede lower ones.
+    #[serde(default)]
+    pub rank: u32,
+    /// Questions the bot asks one at a time when an interview for the tier starts. Reading
+    /// the answers needs the privileged Message Content intent.
+    #[serde(default)]
+    pub questions: Vec<String>,
+    /// Whether interviewees verify their ID for the tier, in which case the attachments
+    /// they post are deleted when the interview ends and only their hashes are kept.
+    #[serde(default)]
+    pub id_verification: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiscordInterviews {
+    /// Minutes after which an ongoing interview is reverted back to pending.
+    #[serde(default = "default_stale_after_minutes")]
+    pub stale_after_minutes: u64,
+    /// Minutes between checks for stale interviews, at least 1.
+    #[serde(default = "default_stale_check_interval_minutes")]
+    pub stale_check_interval_minutes: NonZeroU64,
+    /// Minutes between retries of ID verification attachment purges that failed, at least 1.
+    #[serde(default = "default_purge_retry_interval_minutes")]
+    pub purge_retry_interval_minutes: NonZeroU64,
+    /// What happens to rejected users when they rejoin.
+    #[serde(default)]
+    pub rejected_rejoin: RejectedRejoin,
+    /// What happens when a verified role is granted by hand instead of through an interview.
+    /// Finding out who granted it requires the bot to be able to view the audit log.
+    #[serde(default)]
+    pub manual_grant: ManualGrant,
+}
+
+impl Default for DiscordInterviews {
+    fn default() -> Self {
+        Self {
+            stale_after_minutes: default_stale_after_minutes(),
+            stale_check_interval_minutes: default_stale_check_interval_minutes(),
+            purge_retry_interval_minutes: default_purge_retry_interval_minutes(),
+            rejected_rejoin: RejectedRejoin::default(),
+            manual_grant: ManualGrant::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectedRejoin {
+    /// Rejected users are let in without any roles.
+    #[default]
+    Nothing,
+    /// Rejected users get the role for `rejected_id`.
+    Role,
+    /// Rejected users are kicked.
+    Kick,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManualGrant {
+    /// The grant is recorded as an approval by the moderator who granted the role.
+    #[default]
+    Record,
+    /// The role is removed again and the grant is reported to the marks channel.
+    Revert,
+}
+
+/// Default minutes after which an ongoing interview is considered stale.
+const fn default_stale_after_minutes() -> u64 {
+    24 * 60
+}
+
+/// Default minutes between checks for stale interviews.
+fn default_stale_check_interval_minutes() -> NonZeroU64 {
+    NonZeroU64::new(10).expect("10 is not zero")
+}
+
+fn default_purge_retry_interval_minutes() -> NonZeroU64 {
+    NonZeroU64::new(30).expect("30 is not zero")
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiscordRules {
+    /// When users get marked to be interviewed.
+    #[serde(default)]
+    pub mark_on: MarkOn,
+    /// Whether new members get the pending interview role as soon as they join.
+    #[serde(default)]
+    pub pending_role_on_join: bool,
+    /// Minutes within which further posts of a pending or rejected user are reported to the
+    /// marks channel only once.
+    #[serde(default = "default_report_interval_minutes")]
+    pub report_interval_minutes: u64,
+
+    /// Channels where posting a message marks the user.
+    #[serde(default)]
+    pub watched: WatchedChannels,
+    /// Channels and roles that are never marked.
+    #[serde(default)]
+    pub exempt: ExemptRules,
+}
+
+impl Default for DiscordRules {
+    fn default() -> Self {
+        Self {
+            mark_on: MarkOn::default(),
+            pending_role_on_join: false,
+            report_interval_minutes: default_report_interval_minutes(),
+            watched: WatchedChannels::default(),
+            exempt: ExemptRules::default(),
+        }
+    }
+}
+
+/// Default minutes within which further posts of a user are reported once.
+const fn default_report_interval_minutes() -> u64 {
+    60
+}
+
+impl DiscordRules {
+    /// Whether a channel with the given ID, parent ID and NSFW flag is watched.
+    pub fn is_watched(&self, channel_id: u64, parent_id: Option<u64>, nsfw: bool) -> bool {
+        if self.exempt.channel_ids.contains(&channel_id) {
+            return false;
+        }
+
+        (self.watched.all_nsfw && nsfw)
+            || self.watched.channel_ids.contains(&channel_id)
+            || parent_id.is_some_and(|parent_id| {
+                self.watched.channel_ids.contains(&parent_id)
+                    || self.watched.category_ids.contains(&parent_id)
+            })
+    }
+
+    /// Whether messages of unverified users are deleted in a watched channel with the given ID
+    /// and parent ID.
+    pub fn is_enforced(&self, channel_id: u64, parent_id: Option<u64>) -> bool {
+        self.watched.enforced_ids.contains(&channel_id)
+            || parent_id.is_some_and(|parent_id| self.watched.enforced_ids.contains(&parent_id))
+    }
+
+    /// Whether a member with any of the given role IDs is exempt from being marked.
+    pub fn is_exempt_role(&self, role_ids: impl IntoIterator<Item = u64>) -> bool {
+        role_ids
+            .into_iter()
+            .any(|role_id| self.exempt.role_ids.contains(&role_id))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkOn {
+    /// Mark users when they post their first message in a watched channel.
+    #[default]
+    FirstMessage,
+    /// Mark users as soon as they join the guild.
+    Join,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WatchedChannels {
+    /// Whether every NSFW channel is watched.
+    #[serde(default = "default_all_nsfw")]
+    pub all_nsfw: bool,
+    /// IDs of watched channels.
+    #[serde(default)]
+    pub channel_ids: Vec<u64>,
+    /// IDs of categories whose channels are all watched.
+    #[serde(default)]
+    pub category_ids: Vec<u64>,
+    /// IDs of watched channels or categories where messages of unverified users are deleted.
+    #[serde(default)]
+    pub enforced_ids: Vec<u64>,
+}
+
+impl Default for WatchedChannels {
+    fn default() -> Self {
+        Self {
+            all_nsfw: default_all_nsfw(),
+            channel_ids: Vec::new(),
+            category_ids: Vec::new(),
+            enforced_ids: Vec::new(),
+        }
+    }
+}
+
+/// Every NSFW channel is watched by default.
+const fn default_all_nsfw() -> bool {
+    true
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ExemptRules {
+    /// IDs of roles whose members are never marked.
+    #[serde(default)]
+    pub role_ids: Vec<u64>,
+    /// IDs of channels that are never watched, even if they match other rules.
+    #[serde(default)]
+    pub channel_ids: Vec<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DiscordReconcile {
+    /// Minutes between reconciliations, at least 1. They don't run periodically if not set.
+    #[serde(default)]
+    pub interval_minutes: Option<NonZeroU64>,
 }
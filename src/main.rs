@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use clap::Parser;
 use figment::{
     providers::{Env, Format, Yaml},
@@ -10,31 +12,76 @@ mod cli;
 mod config;
 mod utils;
 
+fn figment(config: &Path) -> anyhow::Result<Figment> {
+    if !config.try_exists()? {
+        anyhow::bail!("Configuration file doesn't exist on the specified path.");
+    }
+
+    Ok(Figment::new()
+        .merge(Yaml::file(config))
+        .merge(Env::raw().split("__")))
+}
+
+async fn connect(database: &PathBuf) -> anyhow::Result<SqlitePool> {
+    utils::database::set_up(database).await?;
+
+    let sqlite_pool = SqlitePool::connect(&format!("sqlite://{}", database.display())).await?;
+    sqlx::migrate!().run(&sqlite_pool).await?;
+
+    Ok(sqlite_pool)
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     match cli::Cli::parse().subcommand {
         cli::SubCommands::Start { config } => {
-            if !config.try_exists()? {
-                anyhow::bail!("Configuration file doesn't exist on the specified path.");
-            }
-
-            let config: config::Config = Figment::new()
-                .merge(Yaml::file(config))
-                .merge(Env::raw().split("__"))
-                .extract()?;
+            let config: config::Config = figment(&config)?.extract()?;
 
             utils::log::set_up(config.logs_folder)?;
 
-            utils::database::set_up(&config.database).await?;
-
-            let sqlite_pool =
-                SqlitePool::connect(&format!("sqlite://{}", config.database.display())).await?;
-            sqlx::migrate!().run(&sqlite_pool).await?;
+            let sqlite_pool = connect(&config.database).await?;
 
             bot::BouncerBot::new(&config.discord.token, sqlite_pool)
                 .start(config.discord)
                 .await?;
         }
+        cli::SubCommands::ExportAuditLog { config, output } => {
+            //* Only the database is needed, so the rest of the configuration isn't required.
+            let config: config::DatabaseConfig = figment(&config)?.extract()?;
+            let sqlite_pool = connect(&config.database).await?;
+
+            match output {
+                Some(output) => {
+                    let mut file = std::io::BufWriter::new(std::fs::File::create(output)?);
+                    bot::export_audit_log(&sqlite_pool, &mut file).await?;
+                }
+                None => bot::export_audit_log(&sqlite_pool, &mut std::io::stdout().lock()).await?,
+            }
+        }
+        cli::SubCommands::Transcript {
+            interview_id,
+            config,
+            format,
+            output,
+        } => {
+            let config: config::DatabaseConfig = figment(&config)?.extract()?;
+            let sqlite_pool = connect(&config.database).await?;
+
+            let format = match format {
+                cli::TranscriptFormat::Markdown => bot::TranscriptFormat::Markdown,
+                cli::TranscriptFormat::Html => bot::TranscriptFormat::Html,
+            };
+            let Some(transcript) =
+                bot::export_transcript(&sqlite_pool, interview_id, format).await?
+            else {
+                anyhow::bail!("Interview `{interview_id}` doesn't exist.");
+            };
+
+            match output {
+                Some(output) => std::fs::write(output, transcript)?,
+                None => print!("{transcript}"),
+            }
+        }
     }
 
     Ok(())